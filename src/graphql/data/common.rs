@@ -1,14 +1,14 @@
 macro_rules! enumerable_enum {
-    ($name:ident { $($value:ident,)* } ) => {
-        #[derive(Clone, Copy, Debug)]
+    ($(#[$meta:meta])* $name:ident { $($value:ident,)* } ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq)]
         pub enum $name { $($value),* }
 
-        use std::slice::Iter;
         impl $name {
             const ENUM_ITEMS: &'static [($name, &'static str)] =
                 &[$(($name::$value, stringify!($value))),*];
 
-            pub fn iter() -> Iter<'static, ($name, &'static str)> {
+            pub fn iter() -> ::std::slice::Iter<'static, ($name, &'static str)> {
                 $name::ENUM_ITEMS.into_iter()
             }
 
@@ -21,6 +21,12 @@ macro_rules! enumerable_enum {
                     $($name::$value => stringify!($value)),*
                 }
             }
+
+            pub fn from_name(name: &str) -> Option<$name> {
+                $name::iter()
+                    .find(|&&(_, value_name)| value_name == name)
+                    .map(|&(value, _)| value)
+            }
         }
     };
 }
@@ -33,8 +39,27 @@ enumerable_enum!(Scalar {
     String,
 });
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum InnerTypeKind {
-    NonVector,
-    Vector { nullable: bool },
-}
+enumerable_enum!(
+    #[allow(non_camel_case_types)]
+    DirectiveLocation {
+        QUERY,
+        MUTATION,
+        SUBSCRIPTION,
+        FIELD,
+        FRAGMENT_DEFINITION,
+        FRAGMENT_SPREAD,
+        INLINE_FRAGMENT,
+        VARIABLE_DEFINITION,
+        SCHEMA,
+        SCALAR,
+        OBJECT,
+        FIELD_DEFINITION,
+        ARGUMENT_DEFINITION,
+        INTERFACE,
+        UNION,
+        ENUM,
+        ENUM_VALUE,
+        INPUT_OBJECT,
+        INPUT_FIELD_DEFINITION,
+    }
+);
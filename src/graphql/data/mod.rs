@@ -1,3 +1,6 @@
 mod common;
+pub mod name_index;
+pub mod owned;
 pub mod processed;
+pub mod string_value;
 pub mod unprocessed;
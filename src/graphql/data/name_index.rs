@@ -0,0 +1,101 @@
+// fields, arguments and enum values are kept in source order, so printed schemes and generated code
+// follow the order chosen by authors; lookups by name use separate index of sorted positions
+use std::slice::Iter;
+
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NameIndex(Vec<usize>);
+
+impl NameIndex {
+    // usable in statics, i.e. for fallback items without fields
+    pub const fn empty() -> NameIndex {
+        NameIndex(Vec::new())
+    }
+
+    pub fn new<T: Named>(items: &[T]) -> NameIndex {
+        let mut positions: Vec<usize> = (0..items.len()).collect();
+        // stable sort, so items with the same name stay in source order
+        positions.sort_by(|&l, &r| items[l].name().cmp(items[r].name()));
+        NameIndex(positions)
+    }
+
+    // source position of item with given name
+    pub fn position<T: Named>(&self, items: &[T], name: &str) -> Option<usize> {
+        self.0
+            .binary_search_by(|&i| items[i].name().cmp(name))
+            .ok()
+            .map(|k| self.0[k])
+    }
+
+    pub fn find<'a, T: Named>(&self, items: &'a [T], name: &str) -> Option<&'a T> {
+        self.position(items, name).map(|i| &items[i])
+    }
+
+    pub fn sorted<'a, T>(&'a self, items: &'a [T]) -> Sorted<'a, T> {
+        Sorted {
+            items,
+            positions: self.0.iter(),
+        }
+    }
+
+    // pairs of items sharing the same name, first one is earlier in source
+    pub fn duplicates<'a, T: Named>(&self, items: &'a [T]) -> Vec<(&'a T, &'a T)> {
+        self.0
+            .windows(2)
+            .map(|pair| (&items[pair[0]], &items[pair[1]]))
+            .filter(|&(first, second)| first.name() == second.name())
+            .collect()
+    }
+}
+
+pub struct Sorted<'a, T: 'a> {
+    items: &'a [T],
+    positions: Iter<'a, usize>,
+}
+
+// derived Clone would require T: Clone
+impl<'a, T> Clone for Sorted<'a, T> {
+    fn clone(&self) -> Sorted<'a, T> {
+        Sorted {
+            items: self.items,
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Sorted<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.positions.next().map(|&i| &self.items[i])
+    }
+}
+
+// items either in source order or sorted by name
+pub enum Ordered<'a, T: 'a> {
+    Source(Iter<'a, T>),
+    Sorted(Sorted<'a, T>),
+}
+
+impl<'a, T> Clone for Ordered<'a, T> {
+    fn clone(&self) -> Ordered<'a, T> {
+        match *self {
+            Ordered::Source(ref iter) => Ordered::Source(iter.clone()),
+            Ordered::Sorted(ref iter) => Ordered::Sorted(iter.clone()),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Ordered<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match *self {
+            Ordered::Source(ref mut iter) => iter.next(),
+            Ordered::Sorted(ref mut iter) => iter.next(),
+        }
+    }
+}
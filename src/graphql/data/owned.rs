@@ -0,0 +1,487 @@
+// owned scheme model, built once when scheme is processed
+// items refer to each other by ItemId, which is index into Scheme items, so the model needs no
+// lifetimes: it can be kept in long-lived state or shared between threads as Arc<Scheme>
+pub use super::common::{DirectiveLocation, Scalar};
+use super::name_index::{NameIndex, Named, Sorted};
+use super::processed::LookupError;
+pub use super::unprocessed::{Directive, EnumValue, Value};
+use super::unprocessed;
+use graphql::scheme;
+use graphql::scheme::GsdlDataMap;
+use std::collections::HashMap;
+
+// valid only for the scheme it is obtained from
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ItemId(usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InnerType {
+    Named(ItemId),
+    List(Box<OuterType>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OuterType {
+    pub nullable: bool,
+    pub inner: InnerType,
+}
+
+impl OuterType {
+    // innermost named type, i.e. Float for [[Float!]!]!
+    pub fn named_type(&self) -> ItemId {
+        match self.inner {
+            InnerType::Named(id) => id,
+            InnerType::List(ref item) => item.named_type(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Argument {
+    pub name: String,
+    pub description: Option<String>,
+    pub argument_type: OuterType,
+    pub default: Option<Value>,
+    pub directives: Vec<Directive>,
+}
+
+impl Named for Argument {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub field_type: OuterType,
+    // in source order
+    pub arguments: Vec<Argument>,
+    argument_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Named for Field {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Field {
+    pub fn argument(&self, name: &str) -> Option<&Argument> {
+        self.argument_index.find(&self.arguments, name)
+    }
+
+    pub fn sorted_arguments(&self) -> Sorted<'_, Argument> {
+        self.argument_index.sorted(&self.arguments)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Enum {
+    pub name: String,
+    pub description: Option<String>,
+    // in source order
+    pub values: Vec<EnumValue>,
+    value_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Enum {
+    pub fn value(&self, name: &str) -> Option<&EnumValue> {
+        self.value_index.find(&self.values, name)
+    }
+
+    pub fn sorted_values(&self) -> Sorted<'_, EnumValue> {
+        self.value_index.sorted(&self.values)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub name: String,
+    pub description: Option<String>,
+    // in source order
+    pub fields: Vec<Argument>,
+    field_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Input {
+    pub fn field(&self, name: &str) -> Option<&Argument> {
+        self.field_index.find(&self.fields, name)
+    }
+
+    pub fn sorted_fields(&self) -> Sorted<'_, Argument> {
+        self.field_index.sorted(&self.fields)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Interface {
+    pub name: String,
+    pub description: Option<String>,
+    pub implements: Vec<ItemId>,
+    // in source order
+    pub fields: Vec<Field>,
+    field_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Interface {
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.field_index.find(&self.fields, name)
+    }
+
+    pub fn sorted_fields(&self) -> Sorted<'_, Field> {
+        self.field_index.sorted(&self.fields)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CustomScalar {
+    pub name: String,
+    pub description: Option<String>,
+    pub specified_by_url: Option<String>,
+    pub directives: Vec<Directive>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Type {
+    pub name: String,
+    pub description: Option<String>,
+    pub implements: Vec<ItemId>,
+    // in source order
+    pub fields: Vec<Field>,
+    field_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Type {
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.field_index.find(&self.fields, name)
+    }
+
+    pub fn sorted_fields(&self) -> Sorted<'_, Field> {
+        self.field_index.sorted(&self.fields)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Union {
+    pub name: String,
+    pub description: Option<String>,
+    pub members: Vec<ItemId>,
+    pub directives: Vec<Directive>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DirectiveDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    // in source order
+    pub arguments: Vec<Argument>,
+    argument_index: NameIndex,
+    pub repeatable: bool,
+    pub locations: Vec<DirectiveLocation>,
+}
+
+impl DirectiveDefinition {
+    pub fn argument(&self, name: &str) -> Option<&Argument> {
+        self.argument_index.find(&self.arguments, name)
+    }
+
+    pub fn sorted_arguments(&self) -> Sorted<'_, Argument> {
+        self.argument_index.sorted(&self.arguments)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Item {
+    Builtin(Scalar),
+    Enum(Enum),
+    Input(Input),
+    Interface(Interface),
+    Scalar(CustomScalar),
+    Type(Type),
+    Union(Union),
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match *self {
+            Item::Builtin(scalar) => Scalar::name(scalar),
+            Item::Enum(ref gsdl_enum) => &gsdl_enum.name,
+            Item::Input(ref input) => &input.name,
+            Item::Interface(ref interface) => &interface.name,
+            Item::Scalar(ref scalar) => &scalar.name,
+            Item::Type(ref gsdl_type) => &gsdl_type.name,
+            Item::Union(ref union) => &union.name,
+        }
+    }
+
+    pub fn as_type(&self) -> Option<&Type> {
+        match *self {
+            Item::Type(ref gsdl_type) => Some(gsdl_type),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scheme {
+    // sorted by name, builtin scalars included
+    items: Vec<Item>,
+    // sorted by name, builtin directives included
+    directive_definitions: Vec<DirectiveDefinition>,
+    query: ItemId,
+    mutate: Option<ItemId>,
+    subscription: Option<ItemId>,
+    description: Option<String>,
+    directives: Vec<Directive>,
+}
+
+impl Scheme {
+    // all references are expected to be checked already, see Processed::from(),
+    // so unknown name or entry point which is not a type means internal data map is inconsistent
+    pub fn from(
+        data_map: &GsdlDataMap,
+        directive_definitions: &[unprocessed::DirectiveDefinition],
+        entry_points: &scheme::Scheme,
+    ) -> Result<Scheme, LookupError> {
+        let mut names: Vec<&String> = data_map.keys().collect();
+        names.sort_unstable();
+        let builder = Builder {
+            ids: names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (name.as_str(), ItemId(i)))
+                .collect(),
+        };
+
+        Ok(Scheme {
+            items: names
+                .iter()
+                .map(|name| builder.item(&data_map[*name]))
+                .collect::<Result<_, _>>()?,
+            directive_definitions: directive_definitions
+                .iter()
+                .map(|d| builder.directive_definition(d))
+                .collect::<Result<_, _>>()?,
+            query: builder.entry_point(&entry_points.query, data_map)?,
+            mutate: builder.optional_entry_point(&entry_points.mutate, data_map)?,
+            subscription: builder.optional_entry_point(&entry_points.subscription, data_map)?,
+            description: entry_points.description.clone(),
+            directives: entry_points.directives.clone(),
+        })
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn item(&self, id: ItemId) -> &Item {
+        &self.items[id.0]
+    }
+
+    pub fn id(&self, name: &str) -> Option<ItemId> {
+        self.items
+            .binary_search_by(|item| item.name().cmp(name))
+            .ok()
+            .map(ItemId)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Item> {
+        self.id(name).map(|id| self.item(id))
+    }
+
+    pub fn directive_definitions(&self) -> &[DirectiveDefinition] {
+        &self.directive_definitions
+    }
+
+    pub fn directive_definition(&self, name: &str) -> Option<&DirectiveDefinition> {
+        find(&self.directive_definitions, name, |d| &d.name)
+    }
+
+    pub fn query(&self) -> &Type {
+        self.entry_point(self.query)
+    }
+
+    pub fn mutate(&self) -> Option<&Type> {
+        self.mutate.map(|id| self.entry_point(id))
+    }
+
+    pub fn subscription(&self) -> Option<&Type> {
+        self.subscription.map(|id| self.entry_point(id))
+    }
+
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    // entry points are checked to be types when scheme is built, see Builder::entry_point()
+    fn entry_point(&self, id: ItemId) -> &Type {
+        match *self.item(id) {
+            Item::Type(ref gsdl_type) => gsdl_type,
+            ref other => unreachable!("Entry point {} is not a type", other.name()),
+        }
+    }
+}
+
+// converts unprocessed data, replacing names with ids
+struct Builder<'a> {
+    ids: HashMap<&'a str, ItemId>,
+}
+
+impl<'a> Builder<'a> {
+    fn id(&self, name: &str) -> Result<ItemId, LookupError> {
+        self.ids
+            .get(name)
+            .cloned()
+            .ok_or_else(|| LookupError::NotFound(String::from(name)))
+    }
+
+    fn entry_point(&self, name: &str, data_map: &GsdlDataMap) -> Result<ItemId, LookupError> {
+        match data_map.get(name) {
+            Some(&unprocessed::GsdlDataItem::Type(_)) => self.id(name),
+            Some(other) => Err(LookupError::wrong_kind(name, "type", other)),
+            None => Err(LookupError::NotFound(String::from(name))),
+        }
+    }
+
+    fn optional_entry_point(
+        &self,
+        name: &Option<String>,
+        data_map: &GsdlDataMap,
+    ) -> Result<Option<ItemId>, LookupError> {
+        match *name {
+            Some(ref name) => Ok(Some(self.entry_point(name, data_map)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn ids(&self, names: &[String]) -> Result<Vec<ItemId>, LookupError> {
+        names.iter().map(|name| self.id(name)).collect()
+    }
+
+    fn outer_type(&self, source: &unprocessed::OuterType) -> Result<OuterType, LookupError> {
+        Ok(OuterType {
+            nullable: source.nullable,
+            inner: match source.inner {
+                unprocessed::InnerType::Named(ref name) => InnerType::Named(self.id(name)?),
+                unprocessed::InnerType::List(ref item) => {
+                    InnerType::List(Box::new(self.outer_type(item)?))
+                }
+            },
+        })
+    }
+
+    fn argument(&self, source: &unprocessed::Argument) -> Result<Argument, LookupError> {
+        Ok(Argument {
+            name: source.name.clone(),
+            description: source.description.clone(),
+            argument_type: self.outer_type(&source.argument_type)?,
+            default: source.default.clone(),
+            directives: source.directives.clone(),
+        })
+    }
+
+    fn arguments(&self, source: &[unprocessed::Argument]) -> Result<Vec<Argument>, LookupError> {
+        source.iter().map(|a| self.argument(a)).collect()
+    }
+
+    fn fields(&self, source: &[unprocessed::Field]) -> Result<Vec<Field>, LookupError> {
+        source
+            .iter()
+            .map(|f| {
+                Ok(Field {
+                    name: f.name.clone(),
+                    description: f.description.clone(),
+                    field_type: self.outer_type(&f.field_type)?,
+                    arguments: self.arguments(&f.arguments)?,
+                    argument_index: f.argument_index.clone(),
+                    directives: f.directives.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn directive_definition(
+        &self,
+        source: &unprocessed::DirectiveDefinition,
+    ) -> Result<DirectiveDefinition, LookupError> {
+        Ok(DirectiveDefinition {
+            name: source.name.clone(),
+            description: source.description.clone(),
+            arguments: self.arguments(&source.arguments)?,
+            argument_index: source.argument_index.clone(),
+            repeatable: source.repeatable,
+            locations: source.locations.clone(),
+        })
+    }
+
+    fn item(&self, source: &unprocessed::GsdlDataItem) -> Result<Item, LookupError> {
+        let item = match *source {
+            unprocessed::GsdlDataItem::Builtin(scalar) => Item::Builtin(scalar),
+            unprocessed::GsdlDataItem::Enum(ref gsdl_enum) => Item::Enum(Enum {
+                name: gsdl_enum.name.clone(),
+                description: gsdl_enum.description.clone(),
+                values: gsdl_enum.values.clone(),
+                value_index: gsdl_enum.value_index.clone(),
+                directives: gsdl_enum.directives.clone(),
+            }),
+            unprocessed::GsdlDataItem::Input(ref input) => Item::Input(Input {
+                name: input.name.clone(),
+                description: input.description.clone(),
+                fields: self.arguments(&input.fields)?,
+                field_index: input.field_index.clone(),
+                directives: input.directives.clone(),
+            }),
+            unprocessed::GsdlDataItem::Interface(ref interface) => Item::Interface(Interface {
+                name: interface.name.clone(),
+                description: interface.description.clone(),
+                implements: self.ids(&interface.implements)?,
+                fields: self.fields(&interface.fields)?,
+                field_index: interface.field_index.clone(),
+                directives: interface.directives.clone(),
+            }),
+            unprocessed::GsdlDataItem::Scalar(ref scalar) => Item::Scalar(CustomScalar {
+                name: scalar.name.clone(),
+                description: scalar.description.clone(),
+                specified_by_url: scalar.specified_by_url().cloned(),
+                directives: scalar.directives.clone(),
+            }),
+            unprocessed::GsdlDataItem::Type(ref gsdl_type) => Item::Type(Type {
+                name: gsdl_type.name.clone(),
+                description: gsdl_type.description.clone(),
+                implements: self.ids(&gsdl_type.implements)?,
+                fields: self.fields(&gsdl_type.fields)?,
+                field_index: gsdl_type.field_index.clone(),
+                directives: gsdl_type.directives.clone(),
+            }),
+            unprocessed::GsdlDataItem::Union(ref union) => Item::Union(Union {
+                name: union.name.clone(),
+                description: union.description.clone(),
+                members: self.ids(&union.members)?,
+                directives: union.directives.clone(),
+            }),
+        };
+        Ok(item)
+    }
+}
+
+// binary search in vector sorted by name
+fn find<'a, T, F>(items: &'a [T], name: &str, item_name: F) -> Option<&'a T>
+where
+    F: Fn(&T) -> &String,
+{
+    items
+        .binary_search_by(|item| item_name(item).as_str().cmp(name))
+        .ok()
+        .map(|i| &items[i])
+}
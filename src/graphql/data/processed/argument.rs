@@ -1,31 +1,69 @@
-use super::super::processed::OuterType;
+use super::directive::{Directive, Directives};
+use super::super::processed::{LookupError, OuterType};
+use super::lookup_error::consistent;
 use super::super::unprocessed;
+use graphql::data::name_index::Ordered;
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
 pub struct Argument<'a> {
     pub name: &'a String,
     pub argument_type: OuterType<'a>,
-    //FIXME add default value support
+    description: Option<&'a String>,
+    default_value: Option<&'a unprocessed::Value>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
 }
 
 impl<'a> Argument<'a> {
-    pub fn from(source: &'a unprocessed::Argument, data_map: &'a GsdlDataMap) -> Argument<'a> {
-        Argument {
+    pub fn from(
+        source: &'a unprocessed::Argument,
+        data_map: &'a GsdlDataMap,
+    ) -> Result<Argument<'a>, LookupError> {
+        Ok(Argument {
             name: &source.name,
-            argument_type: OuterType::from(&source.argument_type, data_map),
-        }
+            argument_type: OuterType::from(&source.argument_type, data_map)?,
+            description: source.description.as_ref(),
+            default_value: source.default.as_ref(),
+            directives_iter: source.directives.iter(),
+        })
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    // value used when argument is not provided, checked against argument type
+    pub fn default_value(&self) -> Option<&'a unprocessed::Value> {
+        self.default_value
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
+
+    // only optional arguments and input fields can be deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.directives().deprecation().is_some()
+    }
+
+    // None unless deprecated with non-null reason
+    pub fn deprecation_reason(&self) -> Option<&'a str> {
+        self.directives().deprecation().and_then(|reason| reason)
     }
 }
 
 pub struct ArgumentIter<'a> {
-    iter: Iter<'a, unprocessed::Argument>,
+    iter: Ordered<'a, unprocessed::Argument>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> ArgumentIter<'a> {
     pub fn from(
-        iter: Iter<'a, unprocessed::Argument>,
+        iter: Ordered<'a, unprocessed::Argument>,
         data_map: &'a GsdlDataMap,
     ) -> ArgumentIter<'a> {
         ArgumentIter { iter, data_map }
@@ -36,17 +74,21 @@ impl<'a> Iterator for ArgumentIter<'a> {
     type Item = Argument<'a>;
 
     fn next(&mut self) -> Option<Argument<'a>> {
-        self.iter.next().map(|a| Argument::from(a, self.data_map))
+        let data_map = self.data_map;
+        self.iter.find_map(|a| consistent(Argument::from(a, data_map)))
     }
 }
 
 pub struct Arguments<'a> {
-    iter: Iter<'a, unprocessed::Argument>,
+    iter: Ordered<'a, unprocessed::Argument>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> Arguments<'a> {
-    pub fn from(iter: Iter<'a, unprocessed::Argument>, data_map: &'a GsdlDataMap) -> Arguments<'a> {
+    pub fn from(
+        iter: Ordered<'a, unprocessed::Argument>,
+        data_map: &'a GsdlDataMap,
+    ) -> Arguments<'a> {
         Arguments { iter, data_map }
     }
 }
@@ -0,0 +1,85 @@
+use super::argument::Argument;
+use super::directive_definition::DirectiveDefinition;
+use super::field::Field;
+use super::value::EnumValue;
+use super::GsdlDataItem;
+
+// scheme element addressed by schema coordinate, see Processed::resolve()
+pub enum SchemaElement<'a> {
+    // Name
+    Item(GsdlDataItem<'a>),
+    // Type.field or Interface.field
+    Field(Field<'a>),
+    // Type.field(argument:) or Interface.field(argument:)
+    FieldArgument(Argument<'a>),
+    // Input.field
+    InputField(Argument<'a>),
+    // Enum.VALUE
+    EnumValue(EnumValue<'a>),
+    // @directive
+    Directive(DirectiveDefinition<'a>),
+    // @directive(argument:)
+    DirectiveArgument(Argument<'a>),
+}
+
+// deprecated element with its schema coordinate, see Processed::deprecations()
+pub struct Deprecation<'a> {
+    pub coordinate: String,
+    // None for explicit null reason
+    pub reason: Option<&'a str>,
+    pub element: SchemaElement<'a>,
+}
+
+// parts of schema coordinate, e.g. User.posts(first:) or @deprecated(reason:)
+pub struct Coordinate<'s> {
+    pub directive: bool,
+    pub name: &'s str,
+    pub member: Option<&'s str>,
+    pub argument: Option<&'s str>,
+}
+
+impl<'s> Coordinate<'s> {
+    pub fn parse(coordinate: &'s str) -> Option<Coordinate<'s>> {
+        let (directive, rest) = if let Some(rest) = coordinate.strip_prefix('@') {
+            (true, rest)
+        } else {
+            (false, coordinate)
+        };
+
+        // step 1: optional argument, always the last part
+        let (path, argument) = match rest.find('(') {
+            Some(i) => {
+                let argument = rest[i + 1..].strip_suffix(":)")?;
+                (&rest[..i], Some(argument))
+            }
+            None => (rest, None),
+        };
+
+        // step 2: name with optional member, directives have no members
+        let (name, member) = match path.find('.') {
+            Some(i) if !directive => (&path[..i], Some(&path[i + 1..])),
+            Some(_) => return None,
+            None => (path, None),
+        };
+
+        // arguments belong to fields or directives
+        if argument.is_some() && member.is_none() && !directive {
+            return None;
+        }
+        let names = [Some(name), member, argument];
+        if names.iter().flatten().any(|name| !is_name(name)) {
+            return None;
+        }
+
+        Some(Coordinate {
+            directive,
+            name,
+            member,
+            argument,
+        })
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
@@ -0,0 +1,97 @@
+use super::super::unprocessed;
+use std::slice::Iter;
+
+// as in the builtin @deprecated definition
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+#[derive(Debug)]
+pub struct Directive<'a> {
+    pub name: &'a String,
+    arguments_iter: Iter<'a, (String, unprocessed::Value)>,
+}
+
+impl<'a> Directive<'a> {
+    pub fn from(source: &'a unprocessed::Directive) -> Directive<'a> {
+        Directive {
+            name: &source.name,
+            arguments_iter: source.arguments.iter(),
+        }
+    }
+
+    // argument values in the order they are written
+    pub fn arguments(&self) -> DirectiveArgumentIter<'a> {
+        DirectiveArgumentIter {
+            iter: self.arguments_iter.clone(),
+        }
+    }
+
+    pub fn argument(&self, name: &str) -> Option<&'a unprocessed::Value> {
+        self.arguments()
+            .find(|&(argument_name, _)| argument_name == name)
+            .map(|(_, value)| value)
+    }
+}
+
+pub struct DirectiveArgumentIter<'a> {
+    iter: Iter<'a, (String, unprocessed::Value)>,
+}
+
+impl<'a> Iterator for DirectiveArgumentIter<'a> {
+    type Item = (&'a String, &'a unprocessed::Value);
+
+    fn next(&mut self) -> Option<(&'a String, &'a unprocessed::Value)> {
+        self.iter.next().map(|argument| (&argument.0, &argument.1))
+    }
+}
+
+pub struct DirectiveIter<'a> {
+    iter: Iter<'a, unprocessed::Directive>,
+}
+
+impl<'a> DirectiveIter<'a> {
+    pub fn from(iter: Iter<'a, unprocessed::Directive>) -> DirectiveIter<'a> {
+        DirectiveIter { iter }
+    }
+}
+
+impl<'a> Iterator for DirectiveIter<'a> {
+    type Item = Directive<'a>;
+
+    fn next(&mut self) -> Option<Directive<'a>> {
+        self.iter.next().map(Directive::from)
+    }
+}
+
+pub struct Directives<'a> {
+    iter: Iter<'a, unprocessed::Directive>,
+}
+
+impl<'a> Directives<'a> {
+    pub fn from(iter: Iter<'a, unprocessed::Directive>) -> Directives<'a> {
+        Directives { iter }
+    }
+
+    // first directive with given name, if any
+    pub fn get(self, name: &str) -> Option<Directive<'a>> {
+        self.into_iter().find(|d| *d.name == name)
+    }
+
+    // outer None means there is no @deprecated, inner None means its reason is null
+    pub fn deprecation(self) -> Option<Option<&'a str>> {
+        self.get("deprecated")
+            .map(|deprecated| match deprecated.argument("reason") {
+                Some(&unprocessed::Value::String(ref reason)) => Some(reason.as_str()),
+                Some(_) => None,
+                None => Some(DEFAULT_DEPRECATION_REASON),
+            })
+    }
+}
+
+impl<'a> IntoIterator for Directives<'a> {
+    type Item = Directive<'a>;
+    type IntoIter = DirectiveIter<'a>;
+
+    fn into_iter(self) -> DirectiveIter<'a> {
+        DirectiveIter::from(self.iter)
+    }
+}
@@ -0,0 +1,102 @@
+use super::argument::{Argument, Arguments};
+use super::super::common::DirectiveLocation;
+use super::lookup_error::consistent;
+use super::super::unprocessed;
+use graphql::data::name_index::{NameIndex, Ordered};
+use graphql::scheme::GsdlDataMap;
+use std::slice::Iter;
+
+pub struct DirectiveDefinition<'a> {
+    pub name: &'a String,
+    pub repeatable: bool,
+    pub locations: &'a [DirectiveLocation],
+    description: Option<&'a String>,
+    arguments_iter: Iter<'a, unprocessed::Argument>,
+    argument_index: &'a NameIndex,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> DirectiveDefinition<'a> {
+    pub fn from(
+        source: &'a unprocessed::DirectiveDefinition,
+        data_map: &'a GsdlDataMap,
+    ) -> DirectiveDefinition<'a> {
+        DirectiveDefinition {
+            name: &source.name,
+            repeatable: source.repeatable,
+            locations: &source.locations,
+            description: source.description.as_ref(),
+            arguments_iter: source.arguments.iter(),
+            argument_index: &source.argument_index,
+            data_map,
+        }
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    // in source order
+    pub fn arguments(&self) -> Arguments<'a> {
+        Arguments::from(Ordered::Source(self.arguments_iter.clone()), self.data_map)
+    }
+
+    pub fn sorted_arguments(&self) -> Arguments<'a> {
+        let arguments = self.argument_index.sorted(self.arguments_iter.as_slice());
+        Arguments::from(Ordered::Sorted(arguments), self.data_map)
+    }
+
+    pub fn argument(&self, name: &str) -> Option<Argument<'a>> {
+        let arguments = self.arguments_iter.as_slice();
+        self.argument_index
+            .position(arguments, name)
+            .and_then(|i| consistent(Argument::from(&arguments[i], self.data_map)))
+    }
+}
+
+pub struct DirectiveDefinitionIter<'a> {
+    iter: Iter<'a, unprocessed::DirectiveDefinition>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> DirectiveDefinitionIter<'a> {
+    pub fn from(
+        iter: Iter<'a, unprocessed::DirectiveDefinition>,
+        data_map: &'a GsdlDataMap,
+    ) -> DirectiveDefinitionIter<'a> {
+        DirectiveDefinitionIter { iter, data_map }
+    }
+}
+
+impl<'a> Iterator for DirectiveDefinitionIter<'a> {
+    type Item = DirectiveDefinition<'a>;
+
+    fn next(&mut self) -> Option<DirectiveDefinition<'a>> {
+        self.iter
+            .next()
+            .map(|d| DirectiveDefinition::from(d, self.data_map))
+    }
+}
+
+pub struct DirectiveDefinitions<'a> {
+    iter: Iter<'a, unprocessed::DirectiveDefinition>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> DirectiveDefinitions<'a> {
+    pub fn from(
+        iter: Iter<'a, unprocessed::DirectiveDefinition>,
+        data_map: &'a GsdlDataMap,
+    ) -> DirectiveDefinitions<'a> {
+        DirectiveDefinitions { iter, data_map }
+    }
+}
+
+impl<'a> IntoIterator for DirectiveDefinitions<'a> {
+    type Item = DirectiveDefinition<'a>;
+    type IntoIter = DirectiveDefinitionIter<'a>;
+
+    fn into_iter(self) -> DirectiveDefinitionIter<'a> {
+        DirectiveDefinitionIter::from(self.iter, self.data_map)
+    }
+}
@@ -1,11 +1,19 @@
-use super::argument::Arguments;
+use super::argument::{Argument, Arguments};
+use super::directive::{Directive, Directives};
+use super::super::processed::{LookupError, OuterType};
+use super::lookup_error::consistent;
 use super::super::unprocessed;
+use graphql::data::name_index::{NameIndex, Ordered};
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
 pub struct Field<'a> {
     pub name: &'a String,
+    field_type: &'a unprocessed::OuterType,
     arguments_iter: Iter<'a, unprocessed::Argument>,
+    argument_index: &'a NameIndex,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
     data_map: &'a GsdlDataMap,
 }
 
@@ -13,23 +21,66 @@ impl<'a> Field<'a> {
     pub fn from(source: &'a unprocessed::Field, data_map: &'a GsdlDataMap) -> Field<'a> {
         Field {
             name: &source.name,
+            field_type: &source.field_type,
             arguments_iter: source.arguments.iter(),
+            argument_index: &source.argument_index,
+            description: source.description.as_ref(),
+            directives_iter: source.directives.iter(),
             data_map,
         }
     }
 
-    pub fn arguments(&self) -> Arguments {
-        Arguments::from(self.arguments_iter.clone(), self.data_map)
+    // return type; lookup fails only for fields created outside of processed scheme
+    pub fn field_type(&self) -> Result<OuterType<'a>, LookupError> {
+        OuterType::from(self.field_type, self.data_map)
+    }
+
+    // in source order
+    pub fn arguments(&self) -> Arguments<'a> {
+        Arguments::from(Ordered::Source(self.arguments_iter.clone()), self.data_map)
+    }
+
+    pub fn sorted_arguments(&self) -> Arguments<'a> {
+        let arguments = self.argument_index.sorted(self.arguments_iter.as_slice());
+        Arguments::from(Ordered::Sorted(arguments), self.data_map)
+    }
+
+    pub fn argument(&self, name: &str) -> Option<Argument<'a>> {
+        let arguments = self.arguments_iter.as_slice();
+        self.argument_index
+            .position(arguments, name)
+            .and_then(|i| consistent(Argument::from(&arguments[i], self.data_map)))
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.directives().deprecation().is_some()
+    }
+
+    // None unless deprecated with non-null reason
+    pub fn deprecation_reason(&self) -> Option<&'a str> {
+        self.directives().deprecation().and_then(|reason| reason)
     }
 }
 
 pub struct FieldIter<'a> {
-    iter: Iter<'a, unprocessed::Field>,
+    iter: Ordered<'a, unprocessed::Field>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> FieldIter<'a> {
-    pub fn from(iter: Iter<'a, unprocessed::Field>, data_map: &'a GsdlDataMap) -> FieldIter<'a> {
+    pub fn from(iter: Ordered<'a, unprocessed::Field>, data_map: &'a GsdlDataMap) -> FieldIter<'a> {
         FieldIter { iter, data_map }
     }
 }
@@ -43,12 +94,12 @@ impl<'a> Iterator for FieldIter<'a> {
 }
 
 pub struct Fields<'a> {
-    iter: Iter<'a, unprocessed::Field>,
+    iter: Ordered<'a, unprocessed::Field>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> Fields<'a> {
-    pub fn from(iter: Iter<'a, unprocessed::Field>, data_map: &'a GsdlDataMap) -> Fields<'a> {
+    pub fn from(iter: Ordered<'a, unprocessed::Field>, data_map: &'a GsdlDataMap) -> Fields<'a> {
         Fields { iter, data_map }
     }
 }
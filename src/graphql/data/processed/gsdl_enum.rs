@@ -1,36 +1,74 @@
+use super::directive::{Directive, Directives};
+use super::lookup_error::{consistent, lookup, LookupError};
 use super::super::unprocessed;
-use super::value::Values;
+use super::value::{EnumValue, Values};
+use graphql::data::name_index::{NameIndex, Ordered};
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
 #[derive(Debug)]
 pub struct Enum<'a> {
     pub name: &'a String,
-    values_iter: Iter<'a, String>,
+    values_iter: Iter<'a, unprocessed::EnumValue>,
+    value_index: &'a NameIndex,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
 }
 
 impl<'a> Enum<'a> {
-    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Enum<'a> {
-        let gsdl_enum = data_map
-            .get(name)
-            .expect(&format!("Enum {} not found in internal data map", name));
-        match *gsdl_enum {
+    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Result<Enum<'a>, LookupError> {
+        match *lookup(name, data_map)? {
             unprocessed::GsdlDataItem::Enum(ref gsdl_enum) => {
-                assert_eq!(*name, gsdl_enum.name);
-                Enum {
+                debug_assert_eq!(*name, gsdl_enum.name);
+                Ok(Enum {
                     name: &gsdl_enum.name,
                     values_iter: gsdl_enum.values.iter(),
-                }
+                    value_index: &gsdl_enum.value_index,
+                    description: gsdl_enum.description.as_ref(),
+                    directives_iter: gsdl_enum.directives.iter(),
+                })
             }
-            _ => panic!(
-                "Expected {} to be enum, but found {:?} instead",
-                name, gsdl_enum
-            ),
+            ref other => Err(LookupError::wrong_kind(name, "enum", other)),
         }
     }
 
-    pub fn values(&self) -> Values {
-        Values::from(self.values_iter.clone())
+    // in source order
+    pub fn values(&self) -> Values<'a> {
+        Values::from(Ordered::Source(self.values_iter.clone()))
+    }
+
+    pub fn sorted_values(&self) -> Values<'a> {
+        Values::from(Ordered::Sorted(
+            self.value_index.sorted(self.values_iter.as_slice()),
+        ))
+    }
+
+    pub fn value(&self, value: &str) -> Option<EnumValue<'a>> {
+        let values = self.values_iter.as_slice();
+        self.value_index
+            .position(values, value)
+            .map(|i| EnumValue::from(&values[i]))
+    }
+
+    // outer None means there is no such value
+    pub fn value_description(&self, value: &str) -> Option<Option<&'a String>> {
+        self.value(value).map(|v| v.description())
+    }
+
+    pub fn value_directives(&self, value: &str) -> Option<Directives<'a>> {
+        self.value(value).map(|v| v.directives())
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
     }
 }
 
@@ -49,7 +87,8 @@ impl<'a> Iterator for EnumIter<'a> {
     type Item = Enum<'a>;
 
     fn next(&mut self) -> Option<Enum<'a>> {
-        self.iter.next().map(|e| Enum::from(e, self.data_map))
+        let data_map = self.data_map;
+        self.iter.find_map(|e| consistent(Enum::from(e, data_map)))
     }
 }
 
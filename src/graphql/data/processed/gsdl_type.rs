@@ -1,36 +1,54 @@
-use super::field::Fields;
+use super::directive::{Directive, Directives};
+use super::field::{Field, Fields};
 use super::interface::Interfaces;
+use super::lookup_error::{consistent, lookup, LookupError};
 use super::super::unprocessed;
+use graphql::data::name_index::{NameIndex, Ordered};
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
+static EMPTY_INDEX: NameIndex = NameIndex::empty();
+
 #[derive(Debug)]
 pub struct Type<'a> {
     pub name: &'a String,
     implements_iter: Iter<'a, String>,
     fields_iter: Iter<'a, unprocessed::Field>,
+    field_index: &'a NameIndex,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> Type<'a> {
-    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Type<'a> {
-        let gsdl_type = data_map
-            .get(name)
-            .expect(&format!("Type {} not found in internal data map", name));
-        match *gsdl_type {
+    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Result<Type<'a>, LookupError> {
+        match *lookup(name, data_map)? {
             unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
-                assert_eq!(*name, gsdl_type.name);
-                Type {
+                debug_assert_eq!(*name, gsdl_type.name);
+                Ok(Type {
                     name: &gsdl_type.name,
                     implements_iter: gsdl_type.implements.iter(),
                     fields_iter: gsdl_type.fields.iter(),
+                    field_index: &gsdl_type.field_index,
+                    description: gsdl_type.description.as_ref(),
+                    directives_iter: gsdl_type.directives.iter(),
                     data_map,
-                }
+                })
             }
-            _ => panic!(
-                "Expected {} to be type, but found {:?} instead",
-                name, gsdl_type
-            ),
+            ref other => Err(LookupError::wrong_kind(name, "type", other)),
+        }
+    }
+
+    // type without fields, returned for entry points of inconsistent scheme in release builds
+    pub fn empty(name: &'a String, data_map: &'a GsdlDataMap) -> Type<'a> {
+        Type {
+            name,
+            implements_iter: [].iter(),
+            fields_iter: [].iter(),
+            field_index: &EMPTY_INDEX,
+            description: None,
+            directives_iter: [].iter(),
+            data_map,
         }
     }
 
@@ -38,8 +56,33 @@ impl<'a> Type<'a> {
         Interfaces::from(self.implements_iter.clone(), self.data_map)
     }
 
-    pub fn fields(&self) -> Fields {
-        Fields::from(self.fields_iter.clone(), self.data_map)
+    // in source order
+    pub fn fields(&self) -> Fields<'a> {
+        Fields::from(Ordered::Source(self.fields_iter.clone()), self.data_map)
+    }
+
+    pub fn sorted_fields(&self) -> Fields<'a> {
+        let fields = self.field_index.sorted(self.fields_iter.as_slice());
+        Fields::from(Ordered::Sorted(fields), self.data_map)
+    }
+
+    pub fn field(&self, name: &str) -> Option<Field<'a>> {
+        let fields = self.fields_iter.as_slice();
+        self.field_index
+            .position(fields, name)
+            .map(|i| Field::from(&fields[i], self.data_map))
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
     }
 }
 
@@ -58,7 +101,8 @@ impl<'a> Iterator for TypeIter<'a> {
     type Item = Type<'a>;
 
     fn next(&mut self) -> Option<Type<'a>> {
-        self.iter.next().map(|t| Type::from(t, self.data_map))
+        let data_map = self.data_map;
+        self.iter.find_map(|t| consistent(Type::from(t, data_map)))
     }
 }
 
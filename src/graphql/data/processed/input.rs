@@ -0,0 +1,105 @@
+use super::directive::{Directive, Directives};
+use super::argument::{Argument, Arguments};
+use super::lookup_error::{consistent, lookup, LookupError};
+use super::super::unprocessed;
+use graphql::data::name_index::{NameIndex, Ordered};
+use graphql::scheme::GsdlDataMap;
+use std::slice::Iter;
+
+#[derive(Debug)]
+pub struct Input<'a> {
+    pub name: &'a String,
+    fields_iter: Iter<'a, unprocessed::Argument>,
+    field_index: &'a NameIndex,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> Input<'a> {
+    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Result<Input<'a>, LookupError> {
+        match *lookup(name, data_map)? {
+            unprocessed::GsdlDataItem::Input(ref input) => {
+                debug_assert_eq!(*name, input.name);
+                Ok(Input {
+                    name: &input.name,
+                    fields_iter: input.fields.iter(),
+                    field_index: &input.field_index,
+                    description: input.description.as_ref(),
+                    directives_iter: input.directives.iter(),
+                    data_map,
+                })
+            }
+            ref other => Err(LookupError::wrong_kind(name, "input", other)),
+        }
+    }
+
+    // in source order
+    pub fn fields(&self) -> Arguments<'a> {
+        Arguments::from(Ordered::Source(self.fields_iter.clone()), self.data_map)
+    }
+
+    pub fn sorted_fields(&self) -> Arguments<'a> {
+        let fields = self.field_index.sorted(self.fields_iter.as_slice());
+        Arguments::from(Ordered::Sorted(fields), self.data_map)
+    }
+
+    pub fn field(&self, name: &str) -> Option<Argument<'a>> {
+        let fields = self.fields_iter.as_slice();
+        self.field_index
+            .position(fields, name)
+            .and_then(|i| consistent(Argument::from(&fields[i], self.data_map)))
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
+}
+
+pub struct InputIter<'a> {
+    iter: Iter<'a, String>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> InputIter<'a> {
+    pub fn from(iter: Iter<'a, String>, data_map: &'a GsdlDataMap) -> InputIter<'a> {
+        InputIter { iter, data_map }
+    }
+}
+
+impl<'a> Iterator for InputIter<'a> {
+    type Item = Input<'a>;
+
+    fn next(&mut self) -> Option<Input<'a>> {
+        let data_map = self.data_map;
+        self.iter.find_map(|i| consistent(Input::from(i, data_map)))
+    }
+}
+
+pub struct Inputs<'a> {
+    iter: Iter<'a, String>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> Inputs<'a> {
+    pub fn from(iter: Iter<'a, String>, data_map: &'a GsdlDataMap) -> Inputs<'a> {
+        Inputs { iter, data_map }
+    }
+}
+
+impl<'a> IntoIterator for Inputs<'a> {
+    type Item = Input<'a>;
+    type IntoIter = InputIter<'a>;
+
+    fn into_iter(self) -> InputIter<'a> {
+        InputIter::from(self.iter, self.data_map)
+    }
+}
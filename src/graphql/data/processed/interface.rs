@@ -1,39 +1,100 @@
-use super::field::Fields;
+use super::directive::{Directive, Directives};
+use super::field::{Field, Fields};
+use super::GsdlDataItem;
+use super::lookup_error::{consistent, lookup, LookupError};
 use super::super::unprocessed;
+use graphql::data::name_index::{NameIndex, Ordered};
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
 #[derive(Debug)]
 pub struct Interface<'a> {
     pub name: &'a String,
+    implements_iter: Iter<'a, String>,
     fields_iter: Iter<'a, unprocessed::Field>,
+    field_index: &'a NameIndex,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> Interface<'a> {
-    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Interface<'a> {
-        let interface = data_map.get(name).expect(&format!(
-            "Interface {} not found in internal data map",
-            name
-        ));
-        match *interface {
+    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Result<Interface<'a>, LookupError> {
+        match *lookup(name, data_map)? {
             unprocessed::GsdlDataItem::Interface(ref interface) => {
-                assert_eq!(*name, interface.name);
-                Interface {
+                debug_assert_eq!(*name, interface.name);
+                Ok(Interface {
                     name: &interface.name,
+                    implements_iter: interface.implements.iter(),
                     fields_iter: interface.fields.iter(),
+                    field_index: &interface.field_index,
+                    description: interface.description.as_ref(),
+                    directives_iter: interface.directives.iter(),
                     data_map,
-                }
+                })
             }
-            _ => panic!(
-                "Expected {} to be interface, but found {:?} instead",
-                name, interface
-            ),
+            ref other => Err(LookupError::wrong_kind(name, "interface", other)),
         }
     }
 
-    pub fn fields(&self) -> Fields {
-        Fields::from(self.fields_iter.clone(), self.data_map)
+    pub fn implements(&self) -> Interfaces<'a> {
+        Interfaces::from(self.implements_iter.clone(), self.data_map)
+    }
+
+    // types and interfaces declaring that they implement this interface, sorted by name
+    pub fn implementations(&self) -> Vec<GsdlDataItem<'a>> {
+        let name = self.name;
+        let mut names: Vec<&'a String> = self.data_map
+            .values()
+            .filter_map(|item| match *item {
+                unprocessed::GsdlDataItem::Type(ref gsdl_type)
+                    if gsdl_type.implements.contains(name) =>
+                {
+                    Some(&gsdl_type.name)
+                }
+                unprocessed::GsdlDataItem::Interface(ref interface)
+                    if interface.implements.contains(name) =>
+                {
+                    Some(&interface.name)
+                }
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        let data_map = self.data_map;
+        names
+            .into_iter()
+            .filter_map(|name| consistent(GsdlDataItem::from(name, data_map)))
+            .collect()
+    }
+
+    // in source order
+    pub fn fields(&self) -> Fields<'a> {
+        Fields::from(Ordered::Source(self.fields_iter.clone()), self.data_map)
+    }
+
+    pub fn sorted_fields(&self) -> Fields<'a> {
+        let fields = self.field_index.sorted(self.fields_iter.as_slice());
+        Fields::from(Ordered::Sorted(fields), self.data_map)
+    }
+
+    pub fn field(&self, name: &str) -> Option<Field<'a>> {
+        let fields = self.fields_iter.as_slice();
+        self.field_index
+            .position(fields, name)
+            .map(|i| Field::from(&fields[i], self.data_map))
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
     }
 }
 
@@ -52,7 +113,8 @@ impl<'a> Iterator for InterfaceIter<'a> {
     type Item = Interface<'a>;
 
     fn next(&mut self) -> Option<Interface<'a>> {
-        self.iter.next().map(|i| Interface::from(i, self.data_map))
+        let data_map = self.data_map;
+        self.iter.find_map(|i| consistent(Interface::from(i, data_map)))
     }
 }
 
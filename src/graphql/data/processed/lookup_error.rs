@@ -0,0 +1,67 @@
+use super::super::unprocessed;
+use graphql::scheme::GsdlDataMap;
+use std::error::Error;
+use std::fmt;
+
+// processed wrappers are created by name, which may be unknown or belong to item of other kind
+#[derive(Clone, Debug, PartialEq)]
+pub enum LookupError {
+    NotFound(String),
+    WrongKind {
+        name: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl LookupError {
+    pub fn wrong_kind(
+        name: &str,
+        expected: &'static str,
+        found: &unprocessed::GsdlDataItem,
+    ) -> LookupError {
+        LookupError::WrongKind {
+            name: String::from(name),
+            expected,
+            found: found.kind(),
+        }
+    }
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LookupError::NotFound(ref name) => write!(f, "{} is not defined", name),
+            LookupError::WrongKind {
+                ref name,
+                expected,
+                found,
+            } => write!(f, "Expected {} to be {}, but it is {}", name, expected, found),
+        }
+    }
+}
+
+impl Error for LookupError {}
+
+pub fn lookup<'a>(
+    name: &str,
+    data_map: &'a GsdlDataMap,
+) -> Result<&'a unprocessed::GsdlDataItem, LookupError> {
+    data_map
+        .get(name)
+        .ok_or_else(|| LookupError::NotFound(String::from(name)))
+}
+
+// names stored inside processed scheme are checked when it is built, so failed lookup is our bug:
+// it panics in debug builds, while release builds skip the item
+pub fn consistent<T>(result: Result<T, LookupError>) -> Option<T> {
+    match result {
+        Ok(item) => Some(item),
+        Err(error) => {
+            if cfg!(debug_assertions) {
+                panic!("Internal data map is inconsistent: {}", error);
+            }
+            None
+        }
+    }
+}
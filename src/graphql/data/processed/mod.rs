@@ -1,24 +1,35 @@
 // types used in external API
 
 pub use self::argument::Argument;
-use self::gsdl_enum::Enum;
-pub use self::gsdl_enum::Enums;
+pub use self::coordinate::{Coordinate, Deprecation, SchemaElement};
+pub use self::directive::Directives;
+pub use self::directive_definition::{DirectiveDefinition, DirectiveDefinitions};
+pub use self::field::{Field, Fields};
+pub use self::gsdl_enum::{Enum, Enums};
 pub use self::gsdl_type::Type;
 pub use self::gsdl_type::Types;
-use self::interface::Interface;
-pub use self::interface::Interfaces;
-use self::union::Union;
-pub use self::union::Unions;
-pub use super::common::{InnerTypeKind, Scalar};
+pub use self::input::{Input, Inputs};
+use self::lookup_error::lookup;
+pub use self::lookup_error::{consistent, LookupError};
+pub use self::interface::{Interface, Interfaces};
+pub use self::scalar::{CustomScalar, CustomScalars};
+pub use self::union::{Union, Unions};
+pub use super::common::{DirectiveLocation, Scalar};
 use super::unprocessed;
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
 
 mod argument;
+mod coordinate;
+mod directive;
+mod directive_definition;
 mod field;
 mod gsdl_enum;
 mod gsdl_type;
+mod input;
 mod interface;
+mod lookup_error;
+mod scalar;
 mod union;
 mod value;
 
@@ -26,45 +37,53 @@ mod value;
 pub enum GsdlDataItem<'a> {
     Builtin(Scalar),
     Enum(Enum<'a>),
+    Input(Input<'a>),
     Interface(Interface<'a>),
+    Scalar(CustomScalar<'a>),
     Type(Type<'a>),
     Union(Union<'a>),
 }
 
 impl<'a> GsdlDataItem<'a> {
-    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> GsdlDataItem<'a> {
-        let source = data_map
-            .get(name)
-            .expect(&format!("Item {} not found in internal data map", name));
-        match *source {
+    pub fn from(
+        name: &'a str,
+        data_map: &'a GsdlDataMap,
+    ) -> Result<GsdlDataItem<'a>, LookupError> {
+        let item = match *lookup(name, data_map)? {
             unprocessed::GsdlDataItem::Builtin(scalar) => {
-                assert_eq!(*name, *Scalar::name(scalar));
+                debug_assert_eq!(*name, *Scalar::name(scalar));
                 GsdlDataItem::Builtin(scalar)
             }
             unprocessed::GsdlDataItem::Enum(ref gsdl_enum) => {
-                assert_eq!(*name, gsdl_enum.name);
-                GsdlDataItem::Enum(Enum::from(&gsdl_enum.name, data_map))
+                GsdlDataItem::Enum(Enum::from(&gsdl_enum.name, data_map)?)
+            }
+            unprocessed::GsdlDataItem::Input(ref input) => {
+                GsdlDataItem::Input(Input::from(&input.name, data_map)?)
             }
             unprocessed::GsdlDataItem::Interface(ref interface) => {
-                assert_eq!(*name, interface.name);
-                GsdlDataItem::Interface(Interface::from(&interface.name, data_map))
+                GsdlDataItem::Interface(Interface::from(&interface.name, data_map)?)
+            }
+            unprocessed::GsdlDataItem::Scalar(ref scalar) => {
+                GsdlDataItem::Scalar(CustomScalar::from(&scalar.name, data_map)?)
             }
             unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
-                assert_eq!(*name, gsdl_type.name);
-                GsdlDataItem::Type(Type::from(&gsdl_type.name, data_map))
+                GsdlDataItem::Type(Type::from(&gsdl_type.name, data_map)?)
             }
             unprocessed::GsdlDataItem::Union(ref union) => {
-                assert_eq!(*name, union.name);
-                GsdlDataItem::Union(Union::from(&union.name, data_map))
+                GsdlDataItem::Union(Union::from(&union.name, data_map)?)
             }
-        }
+        };
+        debug_assert_eq!(name, item.name());
+        Ok(item)
     }
 
     pub fn name(&self) -> &str {
         match *self {
             GsdlDataItem::Builtin(scalar) => Scalar::name(scalar),
             GsdlDataItem::Enum(ref gsdl_enum) => gsdl_enum.name,
+            GsdlDataItem::Input(ref input) => input.name,
             GsdlDataItem::Interface(ref interface) => interface.name,
+            GsdlDataItem::Scalar(ref scalar) => scalar.name,
             GsdlDataItem::Type(ref gsdl_type) => gsdl_type.name,
             GsdlDataItem::Union(ref union) => union.name,
         }
@@ -86,9 +105,9 @@ impl<'a> Iterator for GsdlDataItemIter<'a> {
     type Item = GsdlDataItem<'a>;
 
     fn next(&mut self) -> Option<GsdlDataItem<'a>> {
+        let data_map = self.data_map;
         self.iter
-            .next()
-            .map(|s| GsdlDataItem::from(s, self.data_map))
+            .find_map(|s| consistent(GsdlDataItem::from(s, data_map)))
     }
 }
 
@@ -112,16 +131,23 @@ impl<'a> IntoIterator for GsdlDataItems<'a> {
     }
 }
 
-pub struct InnerType<'a> {
-    pub scalar: GsdlDataItem<'a>,
-    pub kind: InnerTypeKind,
+pub enum InnerType<'a> {
+    Named(GsdlDataItem<'a>),
+    List(Box<OuterType<'a>>),
 }
 
 impl<'a> InnerType<'a> {
-    pub fn from(source: &'a unprocessed::InnerType, data_map: &'a GsdlDataMap) -> InnerType<'a> {
-        InnerType {
-            scalar: GsdlDataItem::from(&source.name, data_map),
-            kind: source.kind.clone(),
+    pub fn from(
+        source: &'a unprocessed::InnerType,
+        data_map: &'a GsdlDataMap,
+    ) -> Result<InnerType<'a>, LookupError> {
+        match *source {
+            unprocessed::InnerType::Named(ref name) => {
+                Ok(InnerType::Named(GsdlDataItem::from(name, data_map)?))
+            }
+            unprocessed::InnerType::List(ref item) => {
+                Ok(InnerType::List(Box::new(OuterType::from(item, data_map)?)))
+            }
         }
     }
 }
@@ -132,10 +158,21 @@ pub struct OuterType<'a> {
 }
 
 impl<'a> OuterType<'a> {
-    pub fn from(source: &'a unprocessed::OuterType, data_map: &'a GsdlDataMap) -> OuterType<'a> {
-        OuterType {
+    pub fn from(
+        source: &'a unprocessed::OuterType,
+        data_map: &'a GsdlDataMap,
+    ) -> Result<OuterType<'a>, LookupError> {
+        Ok(OuterType {
             nullable: source.nullable,
-            inner: InnerType::from(&source.inner, data_map),
+            inner: InnerType::from(&source.inner, data_map)?,
+        })
+    }
+
+    // innermost named type, i.e. Float for [[Float!]!]!
+    pub fn named_type(&self) -> &GsdlDataItem<'a> {
+        match self.inner {
+            InnerType::Named(ref gsdl_item) => gsdl_item,
+            InnerType::List(ref item) => item.named_type(),
         }
     }
 }
@@ -0,0 +1,85 @@
+use super::directive::{Directive, Directives};
+use super::lookup_error::{consistent, lookup, LookupError};
+use super::super::unprocessed;
+use graphql::scheme::GsdlDataMap;
+use std::slice::Iter;
+
+#[derive(Debug)]
+pub struct CustomScalar<'a> {
+    pub name: &'a String,
+    pub specified_by_url: Option<&'a String>,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
+}
+
+impl<'a> CustomScalar<'a> {
+    pub fn from(
+        name: &'a str,
+        data_map: &'a GsdlDataMap,
+    ) -> Result<CustomScalar<'a>, LookupError> {
+        match *lookup(name, data_map)? {
+            unprocessed::GsdlDataItem::Scalar(ref scalar) => {
+                debug_assert_eq!(*name, scalar.name);
+                Ok(CustomScalar {
+                    name: &scalar.name,
+                    specified_by_url: scalar.specified_by_url(),
+                    description: scalar.description.as_ref(),
+                    directives_iter: scalar.directives.iter(),
+                })
+            }
+            ref other => Err(LookupError::wrong_kind(name, "custom scalar", other)),
+        }
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
+}
+
+pub struct CustomScalarIter<'a> {
+    iter: Iter<'a, String>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> CustomScalarIter<'a> {
+    pub fn from(iter: Iter<'a, String>, data_map: &'a GsdlDataMap) -> CustomScalarIter<'a> {
+        CustomScalarIter { iter, data_map }
+    }
+}
+
+impl<'a> Iterator for CustomScalarIter<'a> {
+    type Item = CustomScalar<'a>;
+
+    fn next(&mut self) -> Option<CustomScalar<'a>> {
+        let data_map = self.data_map;
+        self.iter.find_map(|s| consistent(CustomScalar::from(s, data_map)))
+    }
+}
+
+pub struct CustomScalars<'a> {
+    iter: Iter<'a, String>,
+    data_map: &'a GsdlDataMap,
+}
+
+impl<'a> CustomScalars<'a> {
+    pub fn from(iter: Iter<'a, String>, data_map: &'a GsdlDataMap) -> CustomScalars<'a> {
+        CustomScalars { iter, data_map }
+    }
+}
+
+impl<'a> IntoIterator for CustomScalars<'a> {
+    type Item = CustomScalar<'a>;
+    type IntoIter = CustomScalarIter<'a>;
+
+    fn into_iter(self) -> CustomScalarIter<'a> {
+        CustomScalarIter::from(self.iter, self.data_map)
+    }
+}
@@ -1,4 +1,6 @@
+use super::directive::{Directive, Directives};
 use super::GsdlDataItems;
+use super::lookup_error::{consistent, lookup, LookupError};
 use super::super::unprocessed;
 use graphql::scheme::GsdlDataMap;
 use std::slice::Iter;
@@ -7,33 +9,43 @@ use std::slice::Iter;
 pub struct Union<'a> {
     pub name: &'a String,
     members_iter: Iter<'a, String>,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
     data_map: &'a GsdlDataMap,
 }
 
 impl<'a> Union<'a> {
-    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Union<'a> {
-        let union = data_map
-            .get(name)
-            .expect(&format!("Union {} not found in internal data map", name));
-        match *union {
+    pub fn from(name: &'a str, data_map: &'a GsdlDataMap) -> Result<Union<'a>, LookupError> {
+        match *lookup(name, data_map)? {
             unprocessed::GsdlDataItem::Union(ref union) => {
-                assert_eq!(*name, union.name);
-                Union {
+                debug_assert_eq!(*name, union.name);
+                Ok(Union {
                     name: &union.name,
                     members_iter: union.members.iter(),
+                    description: union.description.as_ref(),
+                    directives_iter: union.directives.iter(),
                     data_map,
-                }
+                })
             }
-            _ => panic!(
-                "Expected {} to be union, but found {:?} instead",
-                name, union
-            ),
+            ref other => Err(LookupError::wrong_kind(name, "union", other)),
         }
     }
 
     pub fn members(&self) -> GsdlDataItems {
         GsdlDataItems::from(self.members_iter.clone(), self.data_map)
     }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
 }
 
 pub struct UnionIter<'a> {
@@ -51,7 +63,8 @@ impl<'a> Iterator for UnionIter<'a> {
     type Item = Union<'a>;
 
     fn next(&mut self) -> Option<Union<'a>> {
-        self.iter.next().map(|u| Union::from(u, self.data_map))
+        let data_map = self.data_map;
+        self.iter.find_map(|u| consistent(Union::from(u, data_map)))
     }
 }
 
@@ -1,35 +1,76 @@
+use super::directive::{Directive, Directives};
+use super::super::unprocessed;
+use graphql::data::name_index::Ordered;
 use std::slice::Iter;
 
+#[derive(Debug)]
+pub struct EnumValue<'a> {
+    pub name: &'a String,
+    description: Option<&'a String>,
+    directives_iter: Iter<'a, unprocessed::Directive>,
+}
+
+impl<'a> EnumValue<'a> {
+    pub fn from(source: &'a unprocessed::EnumValue) -> EnumValue<'a> {
+        EnumValue {
+            name: &source.name,
+            description: source.description.as_ref(),
+            directives_iter: source.directives.iter(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&'a String> {
+        self.description
+    }
+
+    pub fn directives(&self) -> Directives<'a> {
+        Directives::from(self.directives_iter.clone())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<Directive<'a>> {
+        self.directives().get(name)
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.directives().deprecation().is_some()
+    }
+
+    // None unless deprecated with non-null reason, e.g. for #[deprecated(note = "...")]
+    pub fn deprecation_reason(&self) -> Option<&'a str> {
+        self.directives().deprecation().and_then(|reason| reason)
+    }
+}
+
 pub struct ValueIter<'a> {
-    iter: Iter<'a, String>,
+    iter: Ordered<'a, unprocessed::EnumValue>,
 }
 
 impl<'a> ValueIter<'a> {
-    pub fn from(iter: Iter<'a, String>) -> ValueIter {
+    pub fn from(iter: Ordered<'a, unprocessed::EnumValue>) -> ValueIter<'a> {
         ValueIter { iter }
     }
 }
 
 impl<'a> Iterator for ValueIter<'a> {
-    type Item = &'a String;
+    type Item = EnumValue<'a>;
 
-    fn next(&mut self) -> Option<&'a String> {
-        self.iter.next()
+    fn next(&mut self) -> Option<EnumValue<'a>> {
+        self.iter.next().map(EnumValue::from)
     }
 }
 
 pub struct Values<'a> {
-    iter: Iter<'a, String>,
+    iter: Ordered<'a, unprocessed::EnumValue>,
 }
 
 impl<'a> Values<'a> {
-    pub fn from(iter: Iter<'a, String>) -> Values {
+    pub fn from(iter: Ordered<'a, unprocessed::EnumValue>) -> Values<'a> {
         Values { iter }
     }
 }
 
 impl<'a> IntoIterator for Values<'a> {
-    type Item = &'a String;
+    type Item = EnumValue<'a>;
     type IntoIter = ValueIter<'a>;
 
     fn into_iter(self) -> ValueIter<'a> {
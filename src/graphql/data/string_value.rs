@@ -0,0 +1,113 @@
+// helpers turning GraphQL string literals into their values
+
+// strips surrounding quotes and processes escape sequences of a "string" literal
+pub fn string_value(literal: &str) -> Result<String, String> {
+    debug_assert!(literal.len() >= 2 && literal.starts_with('"') && literal.ends_with('"'));
+    let content = &literal[1..literal.len() - 1];
+
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('/') => result.push('/'),
+            Some('b') => result.push('\u{0008}'),
+            Some('f') => result.push('\u{000C}'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                let unicode = match code_point(&code) {
+                    // high surrogate has to be followed by low one, e.g. \uD83D\uDE00
+                    Some(high @ 0xD800..=0xDBFF) => {
+                        let mut rest = chars.clone();
+                        let is_escape = rest.next() == Some('\\') && rest.next() == Some('u');
+                        let low: String = rest.by_ref().take(4).collect();
+                        match code_point(&low) {
+                            Some(low @ 0xDC00..=0xDFFF) if is_escape => {
+                                chars = rest;
+                                let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                                ::std::char::from_u32(code)
+                            }
+                            _ => None,
+                        }
+                    }
+                    // lone low surrogates are rejected by from_u32
+                    Some(code) => ::std::char::from_u32(code),
+                    None => None,
+                };
+                match unicode {
+                    Some(unicode) => result.push(unicode),
+                    None => {
+                        return Err(format!(
+                            "Invalid unicode escape sequence \\u{} in string {}",
+                            code, literal
+                        ))
+                    }
+                }
+            }
+            Some(other) => {
+                return Err(format!(
+                    "Invalid escape sequence \\{} in string {}",
+                    other, literal
+                ))
+            }
+            None => return Err(format!("Unterminated escape sequence in string {}", literal)),
+        }
+    }
+
+    Ok(result)
+}
+
+// value of 4 hex digits of \uXXXX escape sequence, from_str_radix() alone would accept a sign
+fn code_point(code: &str) -> Option<u32> {
+    if code.len() == 4 && code.bytes().all(|b| b.is_ascii_hexdigit()) {
+        u32::from_str_radix(code, 16).ok()
+    } else {
+        None
+    }
+}
+
+// strips surrounding quotes of a """block string""" literal and removes common indentation,
+// as described by BlockStringValue() in the spec
+pub fn block_string_value(literal: &str) -> String {
+    debug_assert!(literal.len() >= 6 && literal.starts_with("\"\"\"") && literal.ends_with("\"\"\""));
+    let raw = literal[3..literal.len() - 3].replace("\\\"\"\"", "\"\"\"");
+    let lines: Vec<&str> = raw.split("\r\n")
+        .flat_map(|line| line.split(&['\n', '\r'][..]))
+        .collect();
+
+    // step 1: find common indentation of all lines but the first one, ignoring blank lines
+    let indent = |line: &str| line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| indent(line) < line.len())
+        .map(|line| indent(line))
+        .min();
+
+    // step 2: remove common indentation
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match common_indent {
+            Some(common_indent) if i > 0 => &line[common_indent.min(line.len())..],
+            _ => line,
+        })
+        .collect();
+
+    // step 3: remove leading and trailing blank lines
+    let is_blank = |line: &&str| line.chars().all(|c| c == ' ' || c == '\t');
+    let first = lines.iter().position(|line| !is_blank(line));
+    let last = lines.iter().rposition(|line| !is_blank(line));
+    match (first, last) {
+        (Some(first), Some(last)) => lines[first..last + 1].join("\n"),
+        _ => String::new(),
+    }
+}
@@ -1,12 +1,17 @@
 // data used by .lalrpop file
-pub use super::common::{InnerTypeKind, Scalar};
+pub use super::common::{DirectiveLocation, Scalar};
+pub use super::name_index::{NameIndex, Named};
+use graphql::diagnostic::Span;
+use std::fmt;
 use std::vec::Vec;
 
 #[derive(Debug)]
 pub enum GsdlDataItem {
     Builtin(Scalar),
     Enum(Enum),
+    Input(Input),
     Interface(Interface),
+    Scalar(CustomScalar),
     Type(Type),
     Union(Union),
 }
@@ -16,65 +21,353 @@ impl GsdlDataItem {
         match *self {
             GsdlDataItem::Builtin(scalar) => Scalar::name(scalar),
             GsdlDataItem::Enum(ref gsdl_enum) => &gsdl_enum.name,
+            GsdlDataItem::Input(ref input) => &input.name,
             GsdlDataItem::Interface(ref interface) => &interface.name,
+            GsdlDataItem::Scalar(ref scalar) => &scalar.name,
             GsdlDataItem::Type(ref gsdl_type) => &gsdl_type.name,
             GsdlDataItem::Union(ref union) => &union.name,
         }
     }
+
+    // builtin scalars are not defined in source
+    pub fn span(&self) -> Option<Span> {
+        match *self {
+            GsdlDataItem::Builtin(_) => None,
+            GsdlDataItem::Enum(ref gsdl_enum) => Some(gsdl_enum.span),
+            GsdlDataItem::Input(ref input) => Some(input.span),
+            GsdlDataItem::Interface(ref interface) => Some(interface.span),
+            GsdlDataItem::Scalar(ref scalar) => Some(scalar.span),
+            GsdlDataItem::Type(ref gsdl_type) => Some(gsdl_type.span),
+            GsdlDataItem::Union(ref union) => Some(union.span),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            GsdlDataItem::Builtin(_) => "scalar",
+            GsdlDataItem::Enum(_) => "enum",
+            GsdlDataItem::Input(_) => "input",
+            GsdlDataItem::Interface(_) => "interface",
+            GsdlDataItem::Scalar(_) => "scalar",
+            GsdlDataItem::Type(_) => "type",
+            GsdlDataItem::Union(_) => "union",
+        }
+    }
+
+    // can be used for arguments and input fields
+    pub fn is_input_type(&self) -> bool {
+        match *self {
+            GsdlDataItem::Builtin(_)
+            | GsdlDataItem::Enum(_)
+            | GsdlDataItem::Input(_)
+            | GsdlDataItem::Scalar(_) => true,
+            GsdlDataItem::Interface(_) | GsdlDataItem::Type(_) | GsdlDataItem::Union(_) => false,
+        }
+    }
+
+    // can be used for field return types
+    pub fn is_output_type(&self) -> bool {
+        match *self {
+            GsdlDataItem::Builtin(_)
+            | GsdlDataItem::Enum(_)
+            | GsdlDataItem::Interface(_)
+            | GsdlDataItem::Scalar(_)
+            | GsdlDataItem::Type(_)
+            | GsdlDataItem::Union(_) => true,
+            GsdlDataItem::Input(_) => false,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct InnerType {
-    pub name: String,
-    pub kind: InnerTypeKind,
+// type reference: either a named type or a list of another type reference
+#[derive(Clone, Debug, PartialEq)]
+pub enum InnerType {
+    Named(String),
+    List(Box<OuterType>),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct OuterType {
     pub nullable: bool,
     pub inner: InnerType,
 }
 
+impl OuterType {
+    pub fn named(name: &str, nullable: bool) -> OuterType {
+        OuterType {
+            nullable,
+            inner: InnerType::Named(String::from(name)),
+        }
+    }
+
+    pub fn list(item: OuterType, nullable: bool) -> OuterType {
+        OuterType {
+            nullable,
+            inner: InnerType::List(Box::new(item)),
+        }
+    }
+
+    // name of the innermost named type, i.e. Float for [[Float!]!]!
+    pub fn name(&self) -> &str {
+        match self.inner {
+            InnerType::Named(ref name) => name,
+            InnerType::List(ref item) => item.name(),
+        }
+    }
+}
+
+impl fmt::Display for OuterType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            InnerType::Named(ref name) => write!(f, "{}", name)?,
+            InnerType::List(ref item) => write!(f, "[{}]", item)?,
+        }
+        if self.nullable {
+            Ok(())
+        } else {
+            write!(f, "!")
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Enum {
     pub name: String,
-    pub values: Vec<String>,
+    pub span: Span,
+    pub description: Option<String>,
+    pub values: Vec<EnumValue>,
+    pub value_index: NameIndex,
+    pub directives: Vec<Directive>,
 }
 
 impl Enum {
-    pub fn new(name: &str, values: Vec<&str>) -> Enum {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        directives: Vec<Directive>,
+        values: Vec<EnumValue>,
+    ) -> Enum {
         Enum {
             name: String::from(name),
-            values: values.into_iter().map(String::from).collect(),
+            span,
+            description,
+            value_index: NameIndex::new(&values),
+            values,
+            directives,
         }
     }
 }
 
-impl Value {
-    pub fn new(value: &str) -> Value {
-        Value::String(String::from(value))
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub directives: Vec<Directive>,
+}
+
+impl Named for EnumValue {
+    fn name(&self) -> &str {
+        &self.name
     }
 }
 
-// FIXME proper value support
-#[derive(Debug, PartialEq)]
+impl EnumValue {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        directives: Vec<Directive>,
+    ) -> EnumValue {
+        EnumValue {
+            name: String::from(name),
+            span,
+            description,
+            directives,
+        }
+    }
+}
+
+// input value literal, as used by argument defaults and directive arguments
+#[derive(Clone, Debug, PartialEq)]
 pub enum Value {
+    Int(i64),
+    Float(f64),
     String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    List(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn from_int_literal(literal: &str) -> Result<Value, String> {
+        literal
+            .parse()
+            .map(Value::Int)
+            .map_err(|_| format!("Int value {} is out of range", literal))
+    }
+
+    pub fn from_float_literal(literal: &str) -> Result<Value, String> {
+        literal
+            .parse()
+            .map(Value::Float)
+            .map_err(|_| format!("Invalid float value {}", literal))
+    }
+}
+
+// formats value as GraphQL literal
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{:?}", value),
+            Value::String(ref value) => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if c < ' ' => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::Null => write!(f, "null"),
+            Value::Enum(ref value) => write!(f, "{}", value),
+            Value::List(ref values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(ref fields) => {
+                write!(f, "{{")?;
+                for (i, &(ref name, ref value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Directive {
+    pub name: String,
+    pub span: Span,
+    pub arguments: Vec<(String, Value)>,
+}
+
+impl Directive {
+    pub fn new(name: &str, span: Span, arguments: Option<Vec<(String, Value)>>) -> Directive {
+        Directive {
+            name: String::from(name),
+            span,
+            arguments: arguments.unwrap_or_default(),
+        }
+    }
+
+    pub fn argument(&self, name: &str) -> Option<&Value> {
+        self.arguments
+            .iter()
+            .find(|argument| argument.0 == name)
+            .map(|argument| &argument.1)
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectiveDefinition {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub arguments: Vec<Argument>,
+    pub argument_index: NameIndex,
+    pub repeatable: bool,
+    pub locations: Vec<DirectiveLocation>,
+}
+
+impl DirectiveDefinition {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        arguments: Option<Vec<Argument>>,
+        repeatable: bool,
+        location_head: &str,
+        location_tail: Vec<&str>,
+    ) -> Result<DirectiveDefinition, String> {
+        let mut locations = Vec::with_capacity(location_tail.len() + 1);
+        for location in Some(location_head).into_iter().chain(location_tail) {
+            match DirectiveLocation::from_name(location) {
+                Some(location) => locations.push(location),
+                None => {
+                    return Err(format!(
+                        "Directive @{} uses unknown location {}",
+                        name, location
+                    ))
+                }
+            }
+        }
+        let arguments = arguments.unwrap_or_default();
+        Ok(DirectiveDefinition {
+            name: String::from(name),
+            span,
+            description,
+            argument_index: NameIndex::new(&arguments),
+            arguments,
+            repeatable,
+            locations,
+        })
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Argument {
     pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
     pub argument_type: OuterType,
     pub default: Option<Value>,
+    pub directives: Vec<Directive>,
+}
+
+impl Named for Argument {
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl Argument {
-    pub fn new(name: &str, argument_type: OuterType, default: Option<Value>) -> Argument {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        argument_type: OuterType,
+        default: Option<Value>,
+        directives: Vec<Directive>,
+    ) -> Argument {
         Argument {
             name: String::from(name),
+            span,
+            description,
             argument_type,
             default,
+            directives,
         }
     }
 }
@@ -82,16 +375,68 @@ impl Argument {
 #[derive(Debug, PartialEq)]
 pub struct Field {
     pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
     pub field_type: OuterType,
     pub arguments: Vec<Argument>,
+    pub argument_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Named for Field {
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl Field {
-    pub fn new(name: &str, field_type: OuterType, arguments: Option<Vec<Argument>>) -> Field {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        field_type: OuterType,
+        arguments: Option<Vec<Argument>>,
+        directives: Vec<Directive>,
+    ) -> Field {
+        let arguments = arguments.unwrap_or_default();
         Field {
             name: String::from(name),
+            span,
+            description,
             field_type,
-            arguments: arguments.unwrap_or_default(),
+            argument_index: NameIndex::new(&arguments),
+            arguments,
+            directives,
+        }
+    }
+}
+
+// input fields share the argument syntax (InputValueDefinition in the spec), so Argument is reused
+#[derive(Debug)]
+pub struct Input {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub fields: Vec<Argument>,
+    pub field_index: NameIndex,
+    pub directives: Vec<Directive>,
+}
+
+impl Input {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        directives: Vec<Directive>,
+        fields: Vec<Argument>,
+    ) -> Input {
+        Input {
+            name: String::from(name),
+            span,
+            description,
+            field_index: NameIndex::new(&fields),
+            fields,
+            directives,
         }
     }
 }
@@ -99,35 +444,107 @@ impl Field {
 #[derive(Debug)]
 pub struct Interface {
     pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub implements: Vec<String>,
     pub fields: Vec<Field>,
+    pub field_index: NameIndex,
+    pub directives: Vec<Directive>,
 }
 
 impl Interface {
-    pub fn new(name: &str, fields: Vec<Field>) -> Interface {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        implements: Option<Vec<&str>>,
+        directives: Vec<Directive>,
+        fields: Vec<Field>,
+    ) -> Interface {
         Interface {
             name: String::from(name),
+            span,
+            description,
+            implements: implements
+                .unwrap_or_default()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            field_index: NameIndex::new(&fields),
             fields,
+            directives,
         }
     }
 }
 
+// user defined scalar, as opposed to builtin Scalar
+#[derive(Debug)]
+pub struct CustomScalar {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub directives: Vec<Directive>,
+}
+
+impl CustomScalar {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        directives: Vec<Directive>,
+    ) -> CustomScalar {
+        CustomScalar {
+            name: String::from(name),
+            span,
+            description,
+            directives,
+        }
+    }
+
+    // url from @specifiedBy(url: "...") directive, if any
+    pub fn specified_by_url(&self) -> Option<&String> {
+        self.directives
+            .iter()
+            .find(|d| d.name == "specifiedBy")
+            .and_then(|d| match d.argument("url") {
+                Some(Value::String(url)) => Some(url),
+                _ => None,
+            })
+    }
+}
+
 #[derive(Debug)]
 pub struct Type {
     pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
     pub implements: Vec<String>,
     pub fields: Vec<Field>,
+    pub field_index: NameIndex,
+    pub directives: Vec<Directive>,
 }
 
 impl Type {
-    pub fn new(name: &str, implements: Option<Vec<&str>>, fields: Vec<Field>) -> Type {
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        implements: Option<Vec<&str>>,
+        directives: Vec<Directive>,
+        fields: Vec<Field>,
+    ) -> Type {
         Type {
             name: String::from(name),
+            span,
+            description,
             implements: implements
                 .unwrap_or_default()
                 .into_iter()
                 .map(String::from)
                 .collect(),
+            field_index: NameIndex::new(&fields),
             fields,
+            directives,
         }
     }
 }
@@ -135,42 +552,145 @@ impl Type {
 #[derive(Debug)]
 pub struct Union {
     pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
     pub members: Vec<String>,
+    pub directives: Vec<Directive>,
 }
 
 impl Union {
-    pub fn new(name: &str, members_head: &str, members_tail: Vec<&str>) -> Union {
-        let mut members = Vec::<String>::with_capacity(members_tail.len() + 1);
-        members.push(String::from(members_head));
-        members.extend(members_tail.into_iter().map(String::from));
+    pub fn new(
+        description: Option<String>,
+        name: &str,
+        span: Span,
+        directives: Vec<Directive>,
+        members: Vec<&str>,
+    ) -> Union {
         Union {
             name: String::from(name),
-            members,
+            span,
+            description,
+            members: members.into_iter().map(String::from).collect(),
+            directives,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct SchemeEntryPoints {
+    pub span: Span,
+    pub description: Option<String>,
     pub entries: Vec<(String, String)>,
+    pub directives: Vec<Directive>,
 }
 
 impl SchemeEntryPoints {
-    pub fn new(items: Vec<(&str, &str)>) -> SchemeEntryPoints {
+    pub fn new(
+        description: Option<String>,
+        span: Span,
+        directives: Vec<Directive>,
+        items: Vec<(&str, &str)>,
+    ) -> SchemeEntryPoints {
         SchemeEntryPoints {
+            span,
+            description,
             entries: items
                 .into_iter()
                 .map(|(s1, s2)| (String::from(s1), String::from(s2)))
                 .collect(),
+            directives,
+        }
+    }
+}
+
+// extend enum/input/interface/scalar/schema/type/union, parts are appended to the base definition
+// extensions have no description, missing parts are left empty
+#[derive(Debug)]
+pub enum Extension {
+    Enum(Enum),
+    Input(Input),
+    Interface(Interface),
+    Scalar(CustomScalar),
+    Scheme(SchemeEntryPoints),
+    Type(Type),
+    Union(Union),
+}
+
+impl Extension {
+    // as in GsdlDataItem::kind(), "schema" for schema extension
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Extension::Enum(_) => "enum",
+            Extension::Input(_) => "input",
+            Extension::Interface(_) => "interface",
+            Extension::Scalar(_) => "scalar",
+            Extension::Scheme(_) => "schema",
+            Extension::Type(_) => "type",
+            Extension::Union(_) => "union",
+        }
+    }
+
+    // e.g. "type Query" or "schema", as used in messages
+    pub fn owner(&self) -> String {
+        let name = match *self {
+            Extension::Enum(ref gsdl_enum) => &gsdl_enum.name,
+            Extension::Input(ref input) => &input.name,
+            Extension::Interface(ref interface) => &interface.name,
+            Extension::Scalar(ref scalar) => &scalar.name,
+            Extension::Scheme(_) => return String::from("schema"),
+            Extension::Type(ref gsdl_type) => &gsdl_type.name,
+            Extension::Union(ref union) => &union.name,
+        };
+        format!("{} {}", self.kind(), name)
+    }
+
+    // the spec requires extension to add something
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            Extension::Enum(ref gsdl_enum) => {
+                gsdl_enum.directives.is_empty() && gsdl_enum.values.is_empty()
+            }
+            Extension::Input(ref input) => input.directives.is_empty() && input.fields.is_empty(),
+            Extension::Interface(ref interface) => {
+                interface.implements.is_empty()
+                    && interface.directives.is_empty()
+                    && interface.fields.is_empty()
+            }
+            Extension::Scalar(ref scalar) => scalar.directives.is_empty(),
+            Extension::Scheme(ref scheme) => {
+                scheme.directives.is_empty() && scheme.entries.is_empty()
+            }
+            Extension::Type(ref gsdl_type) => {
+                gsdl_type.implements.is_empty()
+                    && gsdl_type.directives.is_empty()
+                    && gsdl_type.fields.is_empty()
+            }
+            Extension::Union(ref union) => union.directives.is_empty() && union.members.is_empty(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match *self {
+            Extension::Enum(ref gsdl_enum) => gsdl_enum.span,
+            Extension::Input(ref input) => input.span,
+            Extension::Interface(ref interface) => interface.span,
+            Extension::Scalar(ref scalar) => scalar.span,
+            Extension::Scheme(ref scheme) => scheme.span,
+            Extension::Type(ref gsdl_type) => gsdl_type.span,
+            Extension::Union(ref union) => union.span,
         }
     }
 }
 
 // used in .lalrpop
 pub enum GsdlItem {
+    DirectiveDefinition(DirectiveDefinition),
+    Input(Input),
     Interface(Interface),
+    Scalar(CustomScalar),
     Type(Type),
     Enum(Enum),
     Union(Union),
     SchemeEntryPoints(SchemeEntryPoints),
+    Extension(Extension),
 }
@@ -0,0 +1,220 @@
+use std::fmt;
+
+// kind of problem, stable enough to be matched on by tools
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Code {
+    Syntax,
+    DuplicateDefinition,
+    UndefinedReference,
+    WrongKind,
+    InterfaceMismatch,
+    InvalidSchema,
+    InvalidDirective,
+    InvalidValue,
+    ReservedName,
+}
+
+impl Code {
+    pub fn name(self) -> &'static str {
+        match self {
+            Code::Syntax => "syntax",
+            Code::DuplicateDefinition => "duplicate-definition",
+            Code::UndefinedReference => "undefined-reference",
+            Code::WrongKind => "wrong-kind",
+            Code::InterfaceMismatch => "interface-mismatch",
+            Code::InvalidSchema => "invalid-schema",
+            Code::InvalidDirective => "invalid-directive",
+            Code::InvalidValue => "invalid-value",
+            Code::ReservedName => "reserved-name",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+// byte range in the original source
+// line and column (both 1-based) of the start are filled by locate(), parse_gsdl() does it for all
+// returned diagnostics
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span {
+            start,
+            end,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    pub fn locate(&mut self, source: &str) {
+        let start = self.start.min(source.len());
+        let before = &source[..start];
+        self.line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.column = before[line_start..].chars().count() + 1;
+    }
+}
+
+// secondary span with explanation, e.g. "first defined here"
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub code: Code,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Option<Span>,
+    pub labels: Vec<Label>,
+}
+
+impl Diagnostic {
+    pub fn error(code: Code, message: String) -> Diagnostic {
+        Diagnostic {
+            code,
+            severity: Severity::Error,
+            message,
+            span: None,
+            labels: vec![],
+        }
+    }
+
+    // reported at the later definition, pointing to the earlier one
+    pub fn duplicate(message: String, first: Span, second: Span) -> Diagnostic {
+        let (first, second) = if first.start <= second.start {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        Diagnostic::error(Code::DuplicateDefinition, message)
+            .with_span(second)
+            .with_label(first, "first defined here")
+    }
+
+    pub fn with_span(mut self, span: Span) -> Diagnostic {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: String::from(message),
+        });
+        self
+    }
+
+    pub fn locate(mut self, source: &str) -> Diagnostic {
+        if let Some(ref mut span) = self.span {
+            span.locate(source);
+        }
+        for label in &mut self.labels {
+            label.span.locate(source);
+        }
+        self
+    }
+
+    // for sources without text, e.g. introspection results, whose items have empty spans
+    pub fn unlocated(mut self) -> Diagnostic {
+        self.span = None;
+        self.labels.clear();
+        self
+    }
+
+    // renders diagnostic for terminal output, underlining spans in source lines:
+    //
+    // error[duplicate-definition]: Type Query is already defined
+    //  --> 2:6
+    //   |
+    // 2 | type Query { b: Int }
+    //   |      ^^^^^
+    pub fn render(&self, source: &str) -> String {
+        let mut result = format!(
+            "{}[{}]: {}\n",
+            self.severity.name(),
+            self.code.name(),
+            self.message
+        );
+        if let Some(span) = self.span {
+            render_snippet(&mut result, source, span, "-->", '^', "");
+        }
+        for label in &self.labels {
+            render_snippet(&mut result, source, label.span, ":::", '-', &label.message);
+        }
+        result
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(span) if span.line > 0 => write!(
+                f,
+                "{}:{}: {}: {}",
+                span.line,
+                span.column,
+                self.severity.name(),
+                self.message
+            ),
+            _ => write!(f, "{}: {}", self.severity.name(), self.message),
+        }
+    }
+}
+
+fn render_snippet(
+    result: &mut String,
+    source: &str,
+    span: Span,
+    arrow: &str,
+    underline: char,
+    message: &str,
+) {
+    let mut span = span;
+    span.locate(source);
+    let start = span.start.min(source.len());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |i| start + i);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+
+    // spans over several lines are underlined up to the end of the first one
+    let end = span.end.max(start).min(line_start + line.len());
+    let width = source[start..end].chars().count().max(1);
+
+    let number = span.line.to_string();
+    let gutter = " ".repeat(number.len());
+    result.push_str(&format!("{} {} {}:{}\n", gutter, arrow, span.line, span.column));
+    result.push_str(&format!("{} |\n", gutter));
+    result.push_str(&format!("{} | {}\n", number, line));
+    let marker = underline.to_string().repeat(width);
+    let padding = " ".repeat(span.column - 1);
+    if message.is_empty() {
+        result.push_str(&format!("{} | {}{}\n", gutter, padding, marker));
+    } else {
+        result.push_str(&format!("{} | {}{} {}\n", gutter, padding, marker, message));
+    }
+}
@@ -0,0 +1,352 @@
+// tokenizer used by the .lalrpop grammar
+// whitespace, line terminators, commas, byte order mark and comments are ignored as the spec says,
+// token locations are byte offsets in the source
+// names are limited to ASCII as the spec says, lenient lexer also accepts Unicode letters
+// and digits
+
+use graphql::diagnostic::{Code, Diagnostic, Span};
+use std::fmt;
+use std::str::CharIndices;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tok<'input> {
+    Name(&'input str),
+    // keywords are reserved only where the grammar expects them, otherwise they are names
+    Directive,
+    Enum,
+    Extend,
+    False,
+    Implements,
+    Input,
+    Interface,
+    Null,
+    On,
+    Repeatable,
+    Scalar,
+    Schema,
+    Scheme,
+    True,
+    Type,
+    Union,
+    // literals are passed as they appear in source, e.g. string value with quotes and escapes
+    IntValue(&'input str),
+    FloatValue(&'input str),
+    StringValue(&'input str),
+    BlockStringValue(&'input str),
+    Bang,
+    LeftParen,
+    RightParen,
+    Colon,
+    Equals,
+    At,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    Pipe,
+    Ampersand,
+    RightBrace,
+}
+
+impl<'input> fmt::Display for Tok<'input> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Tok::Name(text)
+            | Tok::IntValue(text)
+            | Tok::FloatValue(text)
+            | Tok::StringValue(text)
+            | Tok::BlockStringValue(text) => write!(f, "{}", text),
+            Tok::Directive => write!(f, "directive"),
+            Tok::Enum => write!(f, "enum"),
+            Tok::Extend => write!(f, "extend"),
+            Tok::False => write!(f, "false"),
+            Tok::Implements => write!(f, "implements"),
+            Tok::Input => write!(f, "input"),
+            Tok::Interface => write!(f, "interface"),
+            Tok::Null => write!(f, "null"),
+            Tok::On => write!(f, "on"),
+            Tok::Repeatable => write!(f, "repeatable"),
+            Tok::Scalar => write!(f, "scalar"),
+            Tok::Schema => write!(f, "schema"),
+            Tok::Scheme => write!(f, "scheme"),
+            Tok::True => write!(f, "true"),
+            Tok::Type => write!(f, "type"),
+            Tok::Union => write!(f, "union"),
+            Tok::Bang => write!(f, "!"),
+            Tok::LeftParen => write!(f, "("),
+            Tok::RightParen => write!(f, ")"),
+            Tok::Colon => write!(f, ":"),
+            Tok::Equals => write!(f, "="),
+            Tok::At => write!(f, "@"),
+            Tok::LeftBracket => write!(f, "["),
+            Tok::RightBracket => write!(f, "]"),
+            Tok::LeftBrace => write!(f, "{{"),
+            Tok::Pipe => write!(f, "|"),
+            Tok::Ampersand => write!(f, "&"),
+            Tok::RightBrace => write!(f, "}}"),
+        }
+    }
+}
+
+pub type Spanned<'input> = Result<(usize, Tok<'input>, usize), Diagnostic>;
+
+pub struct Lexer<'input> {
+    source: &'input str,
+    chars: CharIndices<'input>,
+    // next character, peeked from chars
+    lookahead: Option<(usize, char)>,
+    lenient: bool,
+}
+
+impl<'input> Lexer<'input> {
+    pub fn new(source: &'input str) -> Lexer<'input> {
+        let mut chars = source.char_indices();
+        let lookahead = chars.next();
+        Lexer {
+            source,
+            chars,
+            lookahead,
+            lenient: false,
+        }
+    }
+
+    pub fn lenient(source: &'input str) -> Lexer<'input> {
+        Lexer {
+            lenient: true,
+            ..Lexer::new(source)
+        }
+    }
+
+    fn bump(&mut self) -> Option<(usize, char)> {
+        let current = self.lookahead;
+        self.lookahead = self.chars.next();
+        current
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.lookahead.map(|(_, c)| c)
+    }
+
+    // offset of the next character
+    fn offset(&self) -> usize {
+        self.lookahead.map_or(self.source.len(), |(i, _)| i)
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> usize {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+        self.offset()
+    }
+
+    fn error(&self, message: String, start: usize, end: usize) -> Option<Spanned<'input>> {
+        Some(Err(
+            Diagnostic::error(Code::Syntax, message).with_span(Span::new(start, end))
+        ))
+    }
+
+    fn name(&mut self, start: usize) -> Option<Spanned<'input>> {
+        let end = self.take_while(|c| c == '_' || c.is_alphanumeric());
+        if !self.lenient && !self.source[start..end].is_ascii() {
+            return self.error(
+                format!(
+                    "Invalid name {}, names can contain only ASCII letters, digits and underscores",
+                    &self.source[start..end]
+                ),
+                start,
+                end,
+            );
+        }
+        let token = match &self.source[start..end] {
+            "directive" => Tok::Directive,
+            "enum" => Tok::Enum,
+            "extend" => Tok::Extend,
+            "false" => Tok::False,
+            "implements" => Tok::Implements,
+            "input" => Tok::Input,
+            "interface" => Tok::Interface,
+            "null" => Tok::Null,
+            "on" => Tok::On,
+            "repeatable" => Tok::Repeatable,
+            "scalar" => Tok::Scalar,
+            "schema" => Tok::Schema,
+            "scheme" => Tok::Scheme,
+            "true" => Tok::True,
+            "type" => Tok::Type,
+            "union" => Tok::Union,
+            name => Tok::Name(name),
+        };
+        Some(Ok((start, token, end)))
+    }
+
+    fn number(&mut self, start: usize, first: char) -> Option<Spanned<'input>> {
+        // step 1: integer part, leading zeros are not allowed
+        let digits_start = if first == '-' { self.offset() } else { start };
+        if first == '-' {
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return self.error(String::from("Expected digit after -"), start, self.offset());
+            }
+            self.bump();
+        }
+        let mut end = self.take_while(|c| c.is_ascii_digit());
+        if self.source[digits_start..end].starts_with('0') && end - digits_start > 1 {
+            return self.error(
+                format!(
+                    "Invalid number {}, leading zeros are not allowed",
+                    &self.source[start..end]
+                ),
+                start,
+                end,
+            );
+        }
+
+        // step 2: optional fractional part and exponent
+        let mut is_float = false;
+        if self.peek() == Some('.') {
+            self.bump();
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return self.error(
+                    format!("Invalid number {}", &self.source[start..self.offset()]),
+                    start,
+                    self.offset(),
+                );
+            }
+            end = self.take_while(|c| c.is_ascii_digit());
+            is_float = true;
+        }
+        if self.peek() == Some('e') || self.peek() == Some('E') {
+            self.bump();
+            if self.peek() == Some('+') || self.peek() == Some('-') {
+                self.bump();
+            }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return self.error(
+                    format!("Invalid number {}", &self.source[start..self.offset()]),
+                    start,
+                    self.offset(),
+                );
+            }
+            end = self.take_while(|c| c.is_ascii_digit());
+            is_float = true;
+        }
+
+        // step 3: number cannot be followed by name start or dot, e.g. 123abc
+        if let Some(c) = self.peek() {
+            if c == '.' || c == '_' || c.is_alphabetic() {
+                let invalid_end = self.take_while(|c| c == '.' || c == '_' || c.is_alphanumeric());
+                return self.error(
+                    format!("Invalid number {}", &self.source[start..invalid_end]),
+                    start,
+                    invalid_end,
+                );
+            }
+        }
+
+        let literal = &self.source[start..end];
+        if is_float {
+            Some(Ok((start, Tok::FloatValue(literal), end)))
+        } else {
+            Some(Ok((start, Tok::IntValue(literal), end)))
+        }
+    }
+
+    // opening quote is already consumed, escapes are validated when value is computed
+    fn string(&mut self, start: usize) -> Option<Spanned<'input>> {
+        loop {
+            match self.bump() {
+                Some((i, '"')) => {
+                    return Some(Ok((
+                        start,
+                        Tok::StringValue(&self.source[start..i + 1]),
+                        i + 1,
+                    )))
+                }
+                Some((_, '\\')) => {
+                    if let Some((_, '\n')) | Some((_, '\r')) | None = self.lookahead {
+                        break;
+                    }
+                    self.bump();
+                }
+                Some((_, '\n')) | Some((_, '\r')) | None => break,
+                Some(_) => (),
+            }
+        }
+        let end = self.offset();
+        self.error(String::from("Unterminated string"), start, end)
+    }
+
+    // opening quotes are already consumed
+    fn block_string(&mut self, start: usize) -> Option<Spanned<'input>> {
+        loop {
+            let rest = &self.source[self.offset()..];
+            if rest.starts_with("\\\"\"\"") {
+                for _ in 0..4 {
+                    self.bump();
+                }
+            } else if rest.starts_with("\"\"\"") {
+                for _ in 0..3 {
+                    self.bump();
+                }
+                let end = self.offset();
+                return Some(Ok((
+                    start,
+                    Tok::BlockStringValue(&self.source[start..end]),
+                    end,
+                )));
+            } else if self.bump().is_none() {
+                return self.error(
+                    String::from("Unterminated block string"),
+                    start,
+                    self.source.len(),
+                );
+            }
+        }
+    }
+}
+
+impl<'input> Iterator for Lexer<'input> {
+    type Item = Spanned<'input>;
+
+    fn next(&mut self) -> Option<Spanned<'input>> {
+        loop {
+            let (start, c) = self.bump()?;
+            let punctuator = match c {
+                // ignored tokens
+                ' ' | '\t' | '\n' | '\r' | ',' | '\u{FEFF}' => continue,
+                '#' => {
+                    self.take_while(|c| c != '\n' && c != '\r');
+                    continue;
+                }
+
+                '!' => Tok::Bang,
+                '(' => Tok::LeftParen,
+                ')' => Tok::RightParen,
+                ':' => Tok::Colon,
+                '=' => Tok::Equals,
+                '@' => Tok::At,
+                '[' => Tok::LeftBracket,
+                ']' => Tok::RightBracket,
+                '{' => Tok::LeftBrace,
+                '|' => Tok::Pipe,
+                '&' => Tok::Ampersand,
+                '}' => Tok::RightBrace,
+
+                '"' if self.source[start..].starts_with("\"\"\"") => {
+                    self.bump();
+                    self.bump();
+                    return self.block_string(start);
+                }
+                '"' => return self.string(start),
+                '-' | '0'..='9' => return self.number(start, c),
+                c if c == '_' || c.is_alphabetic() => return self.name(start),
+                c => {
+                    return self.error(
+                        format!("Unexpected character {:?}", c),
+                        start,
+                        start + c.len_utf8(),
+                    )
+                }
+            };
+            return Some(Ok((start, punctuator, start + c.len_utf8())));
+        }
+    }
+}
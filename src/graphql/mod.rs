@@ -1,14 +1,50 @@
+pub use self::data::name_index::Sorted;
+pub use self::data::owned::{
+    Argument, CustomScalar, Directive, DirectiveDefinition, DirectiveLocation, Enum, EnumValue,
+    Field, InnerType, Input, Interface, Item, ItemId, OuterType, Scalar, Scheme, Type, Union, Value,
+};
+pub use self::diagnostic::{Code, Diagnostic, Label, Severity, Span};
+pub use self::scheme::Processed;
 use graphql::parsable_as_gsdl_item_list::ParsableAsGsdlItemList;
 use graphql::scheme::UnprocessedSource;
 
 mod data;
+mod diagnostic;
+mod lexer;
 mod parsable_as_gsdl_item_list;
 mod scheme;
 
 mod generated_lalrpop;
 
-pub fn parse_gsdl(source: String) -> Result<scheme::Processed, Vec<String>> {
-    source.parse_as_gsdl_item_list()?.build_gsdl()?.process()
+// line and column of returned diagnostics refer to the source
+pub fn parse_gsdl(source: String) -> Result<scheme::Processed, Vec<Diagnostic>> {
+    parse(source, false)
+}
+
+// as parse_gsdl(), but names can contain Unicode letters and digits, as accepted by older versions
+pub fn parse_gsdl_lenient(source: String) -> Result<scheme::Processed, Vec<Diagnostic>> {
+    parse(source, true)
+}
+
+// introspection result, e.g. saved response of another server; diagnostics have no locations
+pub fn parse_introspection_json(source: String) -> Result<scheme::Processed, Vec<Diagnostic>> {
+    serde_json::from_str(&source)
+        .map_err(|error| {
+            vec![Diagnostic::error(Code::Syntax, format!("Invalid JSON: {}", error))]
+        })
+        .and_then(|json| scheme::introspection_items(&json))
+        .and_then(|items| items.build_gsdl())
+        .and_then(|unprocessed| unprocessed.process())
+        .map_err(|errors| errors.into_iter().map(Diagnostic::unlocated).collect())
+}
+
+fn parse(source: String, lenient: bool) -> Result<scheme::Processed, Vec<Diagnostic>> {
+    source
+        .clone()
+        .parse_as_gsdl_item_list(lenient)
+        .and_then(|items| items.build_gsdl())
+        .and_then(|unprocessed| unprocessed.process())
+        .map_err(|errors| errors.into_iter().map(|e| e.locate(&source)).collect())
 }
 
 #[cfg(test)]
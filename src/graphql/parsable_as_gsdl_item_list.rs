@@ -1,38 +1,59 @@
-extern crate regex;
-
 use graphql::data::unprocessed::GsdlItem;
+use graphql::diagnostic::{Code, Diagnostic, Span};
 use graphql::generated_lalrpop::parse_Gsdl;
+use graphql::lexer::Lexer;
+use lalrpop_util::ParseError;
+use std::fmt::Display;
 
+// lenient parsing accepts Unicode letters and digits in names
 pub trait ParsableAsGsdlItemList {
-    fn parse_as_gsdl_item_list(self) -> Result<Vec<GsdlItem>, Vec<String>>;
-
-    // hackish helper methods. In theory, LALRPOP grammar should parse and ignore comments and commas
-    // However, comments and commas can appear pretty much anywhere, so listing them explicitly adds
-    // a lot of visual noise to .lalrpop file. Therefore, comments and commas are removed manually
-    fn cleanup_gsdl(self) -> String;
-    fn remove_commas(self) -> String;
-    fn remove_comments(self) -> String;
-    fn parse_cleaned_gsdl(self) -> Result<Vec<GsdlItem>, Vec<String>>; // wraps lalrpop parse_Gsdl()
+    fn parse_as_gsdl_item_list(self, lenient: bool) -> Result<Vec<GsdlItem>, Vec<Diagnostic>>;
 }
 
 impl ParsableAsGsdlItemList for String {
-    fn parse_as_gsdl_item_list(self) -> Result<Vec<GsdlItem>, Vec<String>> {
-        self.cleanup_gsdl().parse_cleaned_gsdl()
-    }
-
-    fn cleanup_gsdl(self) -> String {
-        self.remove_commas().remove_comments()
-    }
-
-    fn remove_commas(self) -> String {
-        self.replace(",", " ")
+    fn parse_as_gsdl_item_list(self, lenient: bool) -> Result<Vec<GsdlItem>, Vec<Diagnostic>> {
+        let end = self.len();
+        let lexer = if lenient {
+            Lexer::lenient(&self)
+        } else {
+            Lexer::new(&self)
+        };
+        parse_Gsdl(lexer).map_err(|e| vec![syntax_error(e, end)])
     }
+}
 
-    fn remove_comments(self) -> String {
-        let re = regex::Regex::new("#.*").unwrap();
-        String::from(re.replace(self.as_str(), ""))
-    }
-    fn parse_cleaned_gsdl(self) -> Result<Vec<GsdlItem>, Vec<String>> {
-        parse_Gsdl(self.as_str()).map_err(|e| vec![format!("{:?}", e)])
+// end is the source length, used as location of unexpected end of input
+pub fn syntax_error<T: Display>(error: ParseError<usize, T, Diagnostic>, end: usize) -> Diagnostic {
+    let expected = |expected: Vec<String>| {
+        if expected.is_empty() {
+            String::new()
+        } else {
+            format!(", expected one of {}", expected.join(" "))
+        }
+    };
+    match error {
+        ParseError::InvalidToken { location } => {
+            Diagnostic::error(Code::Syntax, String::from("Invalid token"))
+                .with_span(Span::new(location, location + 1))
+        }
+        ParseError::UnrecognizedToken {
+            token: Some((start, token, token_end)),
+            expected: tokens,
+        } => Diagnostic::error(
+            Code::Syntax,
+            format!("Unexpected token `{}`{}", token, expected(tokens)),
+        ).with_span(Span::new(start, token_end)),
+        ParseError::UnrecognizedToken {
+            token: None,
+            expected: tokens,
+        } => Diagnostic::error(
+            Code::Syntax,
+            format!("Unexpected end of input{}", expected(tokens)),
+        ).with_span(Span::new(end, end)),
+        ParseError::ExtraToken {
+            token: (start, token, token_end),
+        } => Diagnostic::error(Code::Syntax, format!("Unexpected token `{}`", token))
+            .with_span(Span::new(start, token_end)),
+        ParseError::User { error } => error,
     }
 }
@@ -0,0 +1,489 @@
+// introspection result as returned by a server for the canonical IntrospectionQuery of graphql-js,
+// i.e. without schema description, specifiedByURL, isRepeatable and deprecated arguments
+// results with these fields can be read as well, so schemes of other servers can be processed
+use graphql::data::processed::{
+    consistent, Argument, DirectiveLocation, Field, GsdlDataItem, InnerType, OuterType, Scalar,
+};
+use graphql::data::unprocessed;
+use graphql::diagnostic::{Code, Diagnostic, Span};
+use graphql::generated_lalrpop::parse_Value;
+use graphql::lexer::Lexer;
+use graphql::parsable_as_gsdl_item_list::syntax_error;
+use graphql::scheme::Processed;
+use serde_json::Value;
+
+// directives defined by every scheme, see BUILTIN_DIRECTIVES
+const BUILTIN_DIRECTIVE_NAMES: &[&str] = &["deprecated", "include", "skip", "specifiedBy"];
+
+impl Processed {
+    // {"__schema": {...}}, i.e. "data" of the response
+    pub fn to_introspection_json(&self) -> Value {
+        // step 1: all named types, builtin and introspection ones included
+        let mut items: Vec<GsdlDataItem> = Scalar::iter()
+            .filter_map(|&(_, name)| self.get(name))
+            .chain(self.enums().into_iter().map(GsdlDataItem::Enum))
+            .chain(self.inputs().into_iter().map(GsdlDataItem::Input))
+            .chain(self.interfaces().into_iter().map(GsdlDataItem::Interface))
+            .chain(self.scalars().into_iter().map(GsdlDataItem::Scalar))
+            .chain(self.types().into_iter().map(GsdlDataItem::Type))
+            .chain(self.unions().into_iter().map(GsdlDataItem::Union))
+            .chain(self.introspection_types())
+            .collect();
+        items.sort_by(|l, r| l.name().cmp(r.name()));
+
+        // step 2: directives, builtin ones included
+        let directives: Vec<Value> = self
+            .directive_definitions()
+            .into_iter()
+            .map(|directive| {
+                json!({
+                    "name": directive.name,
+                    "description": directive.description(),
+                    "locations": directive
+                        .locations
+                        .iter()
+                        .map(|&location| DirectiveLocation::name(location))
+                        .collect::<Vec<_>>(),
+                    "args": input_values(directive.arguments()),
+                })
+            })
+            .collect();
+
+        json!({
+            "__schema": {
+                "queryType": {"name": self.query().name},
+                "mutationType": self.mutate().map(|mutate| json!({"name": mutate.name})),
+                "subscriptionType": self
+                    .subscription()
+                    .map(|subscription| json!({"name": subscription.name})),
+                "types": items.iter().map(|item| self.full_type(item)).collect::<Vec<_>>(),
+                "directives": directives,
+            }
+        })
+    }
+
+    // fields not applicable to the kind are null
+    fn full_type(&self, item: &GsdlDataItem) -> Value {
+        let mut fields = Value::Null;
+        let mut input_fields = Value::Null;
+        let mut interfaces = Value::Null;
+        let mut enum_values = Value::Null;
+        let mut possible_types = Value::Null;
+        let description = match *item {
+            GsdlDataItem::Builtin(_) => None,
+            GsdlDataItem::Enum(ref gsdl_enum) => {
+                enum_values = gsdl_enum
+                    .values()
+                    .into_iter()
+                    .map(|value| {
+                        json!({
+                            "name": value.name,
+                            "description": value.description(),
+                            "isDeprecated": value.is_deprecated(),
+                            "deprecationReason": value.deprecation_reason(),
+                        })
+                    })
+                    .collect();
+                gsdl_enum.description()
+            }
+            GsdlDataItem::Input(ref input) => {
+                input_fields = input_values(input.fields());
+                input.description()
+            }
+            GsdlDataItem::Interface(ref interface) => {
+                fields = output_fields(interface.fields());
+                interfaces = interface
+                    .implements()
+                    .into_iter()
+                    .map(|i| named_type_ref("INTERFACE", i.name))
+                    .collect();
+                possible_types = self.possible_type_refs(interface.name);
+                interface.description()
+            }
+            GsdlDataItem::Scalar(ref scalar) => scalar.description(),
+            GsdlDataItem::Type(ref gsdl_type) => {
+                fields = output_fields(gsdl_type.fields());
+                interfaces = gsdl_type
+                    .implements()
+                    .into_iter()
+                    .map(|i| named_type_ref("INTERFACE", i.name))
+                    .collect();
+                gsdl_type.description()
+            }
+            GsdlDataItem::Union(ref union) => {
+                possible_types = self.possible_type_refs(union.name);
+                union.description()
+            }
+        };
+
+        json!({
+            "kind": kind(item),
+            "name": item.name(),
+            "description": description,
+            "fields": fields,
+            "inputFields": input_fields,
+            "interfaces": interfaces,
+            "enumValues": enum_values,
+            "possibleTypes": possible_types,
+        })
+    }
+
+    fn possible_type_refs(&self, name: &str) -> Value {
+        consistent(self.possible_types(name))
+            .into_iter()
+            .flatten()
+            .map(|t| named_type_ref("OBJECT", t.name))
+            .collect()
+    }
+}
+
+fn kind(item: &GsdlDataItem) -> &'static str {
+    match *item {
+        GsdlDataItem::Builtin(_) | GsdlDataItem::Scalar(_) => "SCALAR",
+        GsdlDataItem::Enum(_) => "ENUM",
+        GsdlDataItem::Input(_) => "INPUT_OBJECT",
+        GsdlDataItem::Interface(_) => "INTERFACE",
+        GsdlDataItem::Type(_) => "OBJECT",
+        GsdlDataItem::Union(_) => "UNION",
+    }
+}
+
+// fields are requested with includeDeprecated: true
+fn output_fields<'a, I: IntoIterator<Item = Field<'a>>>(fields: I) -> Value {
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let field_type = consistent(field.field_type())?;
+            Some(json!({
+                "name": field.name,
+                "description": field.description(),
+                "args": input_values(field.arguments()),
+                "type": type_ref(&field_type),
+                "isDeprecated": field.is_deprecated(),
+                "deprecationReason": field.deprecation_reason(),
+            }))
+        })
+        .collect()
+}
+
+// arguments and input fields are requested without includeDeprecated, deprecated ones are skipped
+fn input_values<'a, I: IntoIterator<Item = Argument<'a>>>(arguments: I) -> Value {
+    arguments
+        .into_iter()
+        .filter(|argument| !argument.is_deprecated())
+        .map(|argument| {
+            json!({
+                "name": argument.name,
+                "description": argument.description(),
+                "type": type_ref(&argument.argument_type),
+                "defaultValue": argument.default_value().map(|value| value.to_string()),
+            })
+        })
+        .collect()
+}
+
+// ofType chain, e.g. NON_NULL -> LIST -> NON_NULL -> SCALAR Int for [Int!]!
+fn type_ref(outer: &OuterType) -> Value {
+    let inner = match outer.inner {
+        InnerType::Named(ref item) => named_type_ref(kind(item), item.name()),
+        InnerType::List(ref item) => {
+            json!({"kind": "LIST", "name": null, "ofType": type_ref(item)})
+        }
+    };
+    if outer.nullable {
+        inner
+    } else {
+        json!({"kind": "NON_NULL", "name": null, "ofType": inner})
+    }
+}
+
+fn named_type_ref(kind: &str, name: &str) -> Value {
+    json!({"kind": kind, "name": name, "ofType": null})
+}
+
+// items of introspection result, either {"__schema": ...} or {"data": {"__schema": ...}}
+// builtin scalars, introspection types and builtin directives are skipped, Processed defines them
+pub fn introspection_items(json: &Value) -> Result<Vec<unprocessed::GsdlItem>, Vec<Diagnostic>> {
+    let schema = &json.get("data").unwrap_or(json)["__schema"];
+    if !schema.is_object() {
+        return Err(vec![invalid(String::from("Introspection result has no __schema"))]);
+    }
+
+    let mut items = vec![];
+    let mut errors = vec![];
+
+    // step 1: entry points, explicit even if named Query, Mutation and Subscription
+    let mut entries = vec![];
+    for &(key, entry) in &[
+        ("queryType", "query"),
+        ("mutationType", "mutation"),
+        ("subscriptionType", "subscription"),
+    ] {
+        if let Some(name) = schema[key]["name"].as_str() {
+            entries.push((entry, name));
+        }
+    }
+    items.push(unprocessed::GsdlItem::SchemeEntryPoints(
+        unprocessed::SchemeEntryPoints::new(description(schema), Span::default(), vec![], entries),
+    ));
+
+    // step 2: named types
+    for item in list(schema, "types") {
+        let item_name = item["name"].as_str().unwrap_or_default();
+        if Scalar::from_name(item_name).is_some() || item_name.starts_with("__") {
+            continue;
+        }
+        match read_item(item) {
+            Ok(item) => items.push(item),
+            Err(message) => errors.push(invalid(message)),
+        }
+    }
+
+    // step 3: directive definitions
+    for directive in list(schema, "directives") {
+        let directive_name = directive["name"].as_str().unwrap_or_default();
+        if BUILTIN_DIRECTIVE_NAMES.contains(&directive_name) {
+            continue;
+        }
+        match read_directive_definition(directive) {
+            Ok(definition) => items.push(unprocessed::GsdlItem::DirectiveDefinition(definition)),
+            Err(message) => errors.push(invalid(message)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(items)
+    } else {
+        Err(errors)
+    }
+}
+
+fn invalid(message: String) -> Diagnostic {
+    Diagnostic::error(Code::Syntax, message)
+}
+
+fn name<'a>(json: &'a Value, owner: &str) -> Result<&'a str, String> {
+    json["name"]
+        .as_str()
+        .ok_or_else(|| format!("{} has no name", owner))
+}
+
+fn description(json: &Value) -> Option<String> {
+    json["description"].as_str().map(String::from)
+}
+
+// missing and null lists are empty
+fn list<'a>(json: &'a Value, key: &str) -> &'a [Value] {
+    json[key].as_array().map_or(&[], |items| items.as_slice())
+}
+
+fn names<'a>(json: &'a Value, key: &str, owner: &str) -> Result<Vec<&'a str>, String> {
+    list(json, key)
+        .iter()
+        .map(|item| name(item, &format!("{} {}", owner, key)))
+        .collect()
+}
+
+// isDeprecated and deprecationReason as @deprecated directive
+fn deprecation(json: &Value) -> Vec<unprocessed::Directive> {
+    if json["isDeprecated"] != true {
+        return vec![];
+    }
+    let reason = match json["deprecationReason"].as_str() {
+        Some(reason) => unprocessed::Value::String(String::from(reason)),
+        None => unprocessed::Value::Null,
+    };
+    vec![unprocessed::Directive::new(
+        "deprecated",
+        Span::default(),
+        Some(vec![(String::from("reason"), reason)]),
+    )]
+}
+
+// ofType chain, e.g. NON_NULL -> LIST -> NON_NULL -> SCALAR Int is [Int!]!
+fn read_type_ref(json: &Value, owner: &str) -> Result<unprocessed::OuterType, String> {
+    match json["kind"].as_str() {
+        Some("NON_NULL") => {
+            let mut item = read_type_ref(&json["ofType"], owner)?;
+            if !item.nullable {
+                return Err(format!("{} has non-null type wrapped in non-null type", owner));
+            }
+            item.nullable = false;
+            Ok(item)
+        }
+        Some("LIST") => {
+            let item = read_type_ref(&json["ofType"], owner)?;
+            Ok(unprocessed::OuterType::list(item, true))
+        }
+        Some(_) => Ok(unprocessed::OuterType::named(name(json, &format!("{} type", owner))?, true)),
+        None => Err(format!("{} has no type", owner)),
+    }
+}
+
+// default values are GraphQL literals, e.g. "10" or "{limit: 10}"
+fn read_input_value(json: &Value, owner: &str) -> Result<unprocessed::Argument, String> {
+    let argument_name = name(json, owner)?;
+    let owner = format!("{} {}", owner, argument_name);
+    let default = match json["defaultValue"].as_str() {
+        Some(literal) => Some(parse_Value(Lexer::new(literal)).map_err(|error| {
+            format!(
+                "{} has invalid default value {}: {}",
+                owner,
+                literal,
+                syntax_error(error, literal.len()).message
+            )
+        })?),
+        None => None,
+    };
+    Ok(unprocessed::Argument::new(
+        description(json),
+        argument_name,
+        Span::default(),
+        read_type_ref(&json["type"], &owner)?,
+        default,
+        deprecation(json),
+    ))
+}
+
+fn read_input_values(
+    json: &Value,
+    key: &str,
+    owner: &str,
+) -> Result<Vec<unprocessed::Argument>, String> {
+    list(json, key)
+        .iter()
+        .map(|item| read_input_value(item, owner))
+        .collect()
+}
+
+fn read_field(json: &Value, owner: &str) -> Result<unprocessed::Field, String> {
+    let field_name = name(json, &format!("{} field", owner))?;
+    let owner = format!("{} field {}", owner, field_name);
+    Ok(unprocessed::Field::new(
+        description(json),
+        field_name,
+        Span::default(),
+        read_type_ref(&json["type"], &owner)?,
+        Some(read_input_values(json, "args", &format!("{} argument", owner))?),
+        deprecation(json),
+    ))
+}
+
+fn read_fields(json: &Value, owner: &str) -> Result<Vec<unprocessed::Field>, String> {
+    list(json, "fields")
+        .iter()
+        .map(|item| read_field(item, owner))
+        .collect()
+}
+
+fn read_item(json: &Value) -> Result<unprocessed::GsdlItem, String> {
+    let item_name = name(json, "Type")?;
+    let span = Span::default();
+    let item = match json["kind"].as_str() {
+        Some("ENUM") => {
+            let values = list(json, "enumValues")
+                .iter()
+                .map(|value| {
+                    Ok(unprocessed::EnumValue::new(
+                        description(value),
+                        name(value, &format!("Enum {} value", item_name))?,
+                        span,
+                        deprecation(value),
+                    ))
+                })
+                .collect::<Result<_, String>>()?;
+            unprocessed::GsdlItem::Enum(unprocessed::Enum::new(
+                description(json),
+                item_name,
+                span,
+                vec![],
+                values,
+            ))
+        }
+        Some("INPUT_OBJECT") => {
+            let owner = format!("Input {} field", item_name);
+            unprocessed::GsdlItem::Input(unprocessed::Input::new(
+                description(json),
+                item_name,
+                span,
+                vec![],
+                read_input_values(json, "inputFields", &owner)?,
+            ))
+        }
+        Some("INTERFACE") => {
+            let owner = format!("Interface {}", item_name);
+            unprocessed::GsdlItem::Interface(unprocessed::Interface::new(
+                description(json),
+                item_name,
+                span,
+                Some(names(json, "interfaces", &owner)?),
+                vec![],
+                read_fields(json, &owner)?,
+            ))
+        }
+        Some("OBJECT") => {
+            let owner = format!("Type {}", item_name);
+            unprocessed::GsdlItem::Type(unprocessed::Type::new(
+                description(json),
+                item_name,
+                span,
+                Some(names(json, "interfaces", &owner)?),
+                vec![],
+                read_fields(json, &owner)?,
+            ))
+        }
+        Some("SCALAR") => {
+            let directives = match json["specifiedByURL"].as_str() {
+                Some(url) => {
+                    let url = unprocessed::Value::String(String::from(url));
+                    vec![unprocessed::Directive::new(
+                        "specifiedBy",
+                        span,
+                        Some(vec![(String::from("url"), url)]),
+                    )]
+                }
+                None => vec![],
+            };
+            unprocessed::GsdlItem::Scalar(unprocessed::CustomScalar::new(
+                description(json),
+                item_name,
+                span,
+                directives,
+            ))
+        }
+        Some("UNION") => {
+            let owner = format!("Union {}", item_name);
+            unprocessed::GsdlItem::Union(unprocessed::Union::new(
+                description(json),
+                item_name,
+                span,
+                vec![],
+                names(json, "possibleTypes", &owner)?,
+            ))
+        }
+        Some(kind) => return Err(format!("Type {} has unknown kind {}", item_name, kind)),
+        None => return Err(format!("Type {} has no kind", item_name)),
+    };
+    Ok(item)
+}
+
+fn read_directive_definition(json: &Value) -> Result<unprocessed::DirectiveDefinition, String> {
+    let directive_name = name(json, "Directive")?;
+    let owner = format!("Directive @{}", directive_name);
+    let locations: Vec<&str> = list(json, "locations")
+        .iter()
+        .map(|location| location.as_str().ok_or_else(|| format!("{} has invalid location", owner)))
+        .collect::<Result<_, String>>()?;
+    let (location_head, location_tail) = locations
+        .split_first()
+        .ok_or_else(|| format!("{} has no locations", owner))?;
+    unprocessed::DirectiveDefinition::new(
+        description(json),
+        directive_name,
+        Span::default(),
+        Some(read_input_values(json, "args", &format!("{} argument", owner))?),
+        json["isRepeatable"] == true,
+        location_head,
+        location_tail.to_vec(),
+    )
+}
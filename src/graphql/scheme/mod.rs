@@ -1,5 +1,7 @@
-pub use self::processed::{GsdlDataMap, Processed};
+pub use self::introspection::introspection_items;
+pub use self::processed::{GsdlDataMap, Processed, Scheme};
 pub use self::unprocessed::{Unprocessed, UnprocessedSource};
 
+mod introspection;
 mod processed;
 mod unprocessed;
@@ -1,11 +1,98 @@
 use graphql::data;
 use graphql::data::unprocessed;
+use graphql::data::unprocessed::DirectiveLocation;
+use graphql::diagnostic::{Code, Diagnostic, Span};
+use graphql::generated_lalrpop::{parse_DirectiveDefinition, parse_Enum, parse_Field, parse_Type};
+use graphql::lexer::Lexer;
 use graphql::scheme::Unprocessed;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+// directives every scheme supports, defined the same way as user directives
+const BUILTIN_DIRECTIVES: &[&str] = &[
+    "directive @deprecated(reason: String = \"No longer supported\") \
+     on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE",
+    "directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT",
+    "directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT",
+    "directive @specifiedBy(url: String!) on SCALAR",
+];
+
+// introspection system types, resolvable by name but not listed with user types
+const INTROSPECTION_TYPES: &[&str] = &[
+    "type __Schema {
+        description: String
+        types: [__Type!]!
+        queryType: __Type!
+        mutationType: __Type
+        subscriptionType: __Type
+        directives: [__Directive!]!
+    }",
+    "type __Type {
+        kind: __TypeKind!
+        name: String
+        description: String
+        fields(includeDeprecated: Boolean = false): [__Field!]
+        interfaces: [__Type!]
+        possibleTypes: [__Type!]
+        enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+        inputFields(includeDeprecated: Boolean = false): [__InputValue!]
+        ofType: __Type
+        specifiedByURL: String
+    }",
+    "type __Field {
+        name: String!
+        description: String
+        args(includeDeprecated: Boolean = false): [__InputValue!]!
+        type: __Type!
+        isDeprecated: Boolean!
+        deprecationReason: String
+    }",
+    "type __InputValue {
+        name: String!
+        description: String
+        type: __Type!
+        defaultValue: String
+        isDeprecated: Boolean!
+        deprecationReason: String
+    }",
+    "type __EnumValue {
+        name: String!
+        description: String
+        isDeprecated: Boolean!
+        deprecationReason: String
+    }",
+    "type __Directive {
+        name: String!
+        description: String
+        locations: [__DirectiveLocation!]!
+        args(includeDeprecated: Boolean = false): [__InputValue!]!
+        isRepeatable: Boolean!
+    }",
+];
+
+const INTROSPECTION_ENUMS: &[&str] = &[
+    "enum __TypeKind { SCALAR OBJECT INTERFACE UNION ENUM INPUT_OBJECT LIST NON_NULL }",
+    "enum __DirectiveLocation {
+        QUERY MUTATION SUBSCRIPTION FIELD FRAGMENT_DEFINITION FRAGMENT_SPREAD INLINE_FRAGMENT
+        VARIABLE_DEFINITION SCHEMA SCALAR OBJECT FIELD_DEFINITION ARGUMENT_DEFINITION INTERFACE
+        UNION ENUM ENUM_VALUE INPUT_OBJECT INPUT_FIELD_DEFINITION
+    }",
+];
+
+// implicit fields of every type, interface and union
+const TYPENAME_FIELD: &str = "__typename: String!";
+
+// implicit fields of query entry point
+const ROOT_META_FIELDS: &[&str] = &["__schema: __Schema!", "__type(name: String!): __Type"];
 
 pub struct Scheme {
     pub query: String,
     pub mutate: Option<String>,
+    pub subscription: Option<String>,
+    pub description: Option<String>,
+    // None for implicit entry points
+    pub span: Option<Span>,
+    pub directives: Vec<unprocessed::Directive>,
 }
 
 pub type GsdlDataMap = HashMap<String, unprocessed::GsdlDataItem>;
@@ -20,22 +107,80 @@ pub type GsdlDataMap = HashMap<String, unprocessed::GsdlDataItem>;
 pub struct Processed {
     // internal structure holding all used data
     data_map: GsdlDataMap,
+    // sorted by name, builtin directives included
+    directive_definitions: Vec<unprocessed::DirectiveDefinition>,
     enums: Vec<String>,
+    inputs: Vec<String>,
     interfaces: Vec<String>,
+    // sorted by name, kept apart so they are not listed with user types
+    introspection_types: Vec<String>,
+    // the same data without references into Processed, built by from() once checks pass
+    owned: Option<Arc<data::owned::Scheme>>,
+    scalars: Vec<String>,
     scheme: Scheme,
+    // __schema and __type
+    root_meta_fields: Vec<unprocessed::Field>,
     types: Vec<String>,
+    typename_field: unprocessed::Field,
     unions: Vec<String>,
 }
 
 impl Processed {
+    pub fn directive_definitions(&self) -> data::processed::DirectiveDefinitions {
+        data::processed::DirectiveDefinitions::from(
+            self.directive_definitions.iter(),
+            &self.data_map,
+        )
+    }
+
+    pub fn directive_definition(&self, name: &str) -> Option<data::processed::DirectiveDefinition> {
+        self.find_directive_definition(name)
+            .map(|d| data::processed::DirectiveDefinition::from(d, &self.data_map))
+    }
+
     pub fn enums(&self) -> data::processed::Enums {
         data::processed::Enums::from(self.enums.iter(), &self.data_map)
     }
 
+    pub fn inputs(&self) -> data::processed::Inputs {
+        data::processed::Inputs::from(self.inputs.iter(), &self.data_map)
+    }
+
     pub fn interfaces(&self) -> data::processed::Interfaces {
         data::processed::Interfaces::from(self.interfaces.iter(), &self.data_map)
     }
 
+    // user defined scalars; builtin ones are not listed
+    pub fn scalars(&self) -> data::processed::CustomScalars {
+        data::processed::CustomScalars::from(self.scalars.iter(), &self.data_map)
+    }
+
+    // __Schema, __Type and other introspection types, which are not listed by types() and enums()
+    pub fn introspection_types(&self) -> data::processed::GsdlDataItems {
+        data::processed::GsdlDataItems::from(self.introspection_types.iter(), &self.data_map)
+    }
+
+    // implicit __typename of types, interfaces and unions, __schema and __type of query
+    pub fn meta_field(&self, parent: &str, name: &str) -> Option<data::processed::Field> {
+        if name == self.typename_field.name {
+            return match self.data_map.get(parent) {
+                Some(&unprocessed::GsdlDataItem::Type(_))
+                | Some(&unprocessed::GsdlDataItem::Interface(_))
+                | Some(&unprocessed::GsdlDataItem::Union(_)) => Some(
+                    data::processed::Field::from(&self.typename_field, &self.data_map),
+                ),
+                _ => None,
+            };
+        }
+        if parent != self.scheme.query {
+            return None;
+        }
+        self.root_meta_fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| data::processed::Field::from(field, &self.data_map))
+    }
+
     pub fn types(&self) -> data::processed::Types {
         data::processed::Types::from(self.types.iter(), &self.data_map)
     }
@@ -44,20 +189,215 @@ impl Processed {
         data::processed::Unions::from(self.unions.iter(), &self.data_map)
     }
 
-    pub fn mutate(&self) -> Option<data::processed::Type> {
-        if let Some(ref mutate) = self.scheme.mutate {
-            Some(data::processed::Type::from(mutate, &self.data_map))
-        } else {
-            None
+    // any named item, builtin scalars included
+    pub fn get(&self, name: &str) -> Option<data::processed::GsdlDataItem> {
+        self.data_map
+            .get_key_value(name)
+            .and_then(|(name, _)| data::processed::GsdlDataItem::from(name, &self.data_map).ok())
+    }
+
+    pub fn get_enum(&self, name: &str) -> Option<data::processed::Enum> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Enum(gsdl_enum)) => Some(gsdl_enum),
+            _ => None,
+        }
+    }
+
+    pub fn get_input(&self, name: &str) -> Option<data::processed::Input> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Input(input)) => Some(input),
+            _ => None,
+        }
+    }
+
+    pub fn get_interface(&self, name: &str) -> Option<data::processed::Interface> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Interface(interface)) => Some(interface),
+            _ => None,
+        }
+    }
+
+    // user defined scalars only, as in scalars()
+    pub fn get_scalar(&self, name: &str) -> Option<data::processed::CustomScalar> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Scalar(scalar)) => Some(scalar),
+            _ => None,
+        }
+    }
+
+    pub fn get_type(&self, name: &str) -> Option<data::processed::Type> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Type(gsdl_type)) => Some(gsdl_type),
+            _ => None,
+        }
+    }
+
+    pub fn get_union(&self, name: &str) -> Option<data::processed::Union> {
+        match self.get(name) {
+            Some(data::processed::GsdlDataItem::Union(union)) => Some(union),
+            _ => None,
+        }
+    }
+
+    // resolves schema coordinate: User, User.posts, User.posts(first:), Role.ADMIN, Filter.at,
+    // @deprecated or @deprecated(reason:)
+    // None for malformed coordinates and for elements that do not exist
+    pub fn resolve(&self, coordinate: &str) -> Option<data::processed::SchemaElement> {
+        use graphql::data::processed::{Coordinate, GsdlDataItem, SchemaElement};
+
+        let coordinate = Coordinate::parse(coordinate)?;
+        if coordinate.directive {
+            let definition = self.directive_definition(coordinate.name)?;
+            return match coordinate.argument {
+                Some(argument) => definition
+                    .argument(argument)
+                    .map(SchemaElement::DirectiveArgument),
+                None => Some(SchemaElement::Directive(definition)),
+            };
+        }
+
+        let item = self.get(coordinate.name)?;
+        let member = match coordinate.member {
+            Some(member) => member,
+            None => return Some(SchemaElement::Item(item)),
+        };
+        let field = match item {
+            GsdlDataItem::Type(ref gsdl_type) => gsdl_type.field(member),
+            GsdlDataItem::Interface(ref interface) => interface.field(member),
+            GsdlDataItem::Input(ref input) if coordinate.argument.is_none() => {
+                return input.field(member).map(SchemaElement::InputField)
+            }
+            GsdlDataItem::Enum(ref gsdl_enum) if coordinate.argument.is_none() => {
+                return gsdl_enum.value(member).map(SchemaElement::EnumValue)
+            }
+            _ => None,
+        }.or_else(|| self.meta_field(coordinate.name, member))?;
+        match coordinate.argument {
+            Some(argument) => field.argument(argument).map(SchemaElement::FieldArgument),
+            None => Some(SchemaElement::Field(field)),
+        }
+    }
+
+    // object types, which can be returned where union or interface is expected:
+    // union members in declaration order or types implementing interface sorted by name
+    pub fn possible_types(
+        &self,
+        abstract_type: &str,
+    ) -> Result<Vec<data::processed::Type>, data::processed::LookupError> {
+        use graphql::data::processed::LookupError;
+
+        match self.data_map.get(abstract_type) {
+            Some(&unprocessed::GsdlDataItem::Union(ref union)) => {
+                Ok(data::processed::Types::from(union.members.iter(), &self.data_map)
+                    .into_iter()
+                    .collect())
+            }
+            Some(&unprocessed::GsdlDataItem::Interface(ref interface)) => Ok(self.types()
+                .into_iter()
+                .filter(|t| t.implements().into_iter().any(|i| *i.name == interface.name))
+                .collect()),
+            Some(other) => Err(LookupError::wrong_kind(
+                abstract_type,
+                "union or interface",
+                other,
+            )),
+            None => Err(LookupError::NotFound(String::from(abstract_type))),
+        }
+    }
+
+    // every deprecated field, argument, input field and enum value, sorted by schema coordinate
+    pub fn deprecations(&self) -> Vec<data::processed::Deprecation> {
+        use graphql::data::processed::{Deprecation, SchemaElement};
+
+        let mut result = vec![];
+        for gsdl_type in self.types() {
+            field_deprecations(gsdl_type.name, gsdl_type.fields(), &mut result);
+        }
+        for interface in self.interfaces() {
+            field_deprecations(interface.name, interface.fields(), &mut result);
+        }
+        for input in self.inputs() {
+            for field in input.fields() {
+                if let Some(reason) = field.directives().deprecation() {
+                    result.push(Deprecation {
+                        coordinate: format!("{}.{}", input.name, field.name),
+                        reason,
+                        element: SchemaElement::InputField(field),
+                    });
+                }
+            }
+        }
+        for gsdl_enum in self.enums() {
+            for value in gsdl_enum.values() {
+                if let Some(reason) = value.directives().deprecation() {
+                    result.push(Deprecation {
+                        coordinate: format!("{}.{}", gsdl_enum.name, value.name),
+                        reason,
+                        element: SchemaElement::EnumValue(value),
+                    });
+                }
+            }
         }
+        for definition in self.directive_definitions() {
+            for argument in definition.arguments() {
+                if let Some(reason) = argument.directives().deprecation() {
+                    result.push(Deprecation {
+                        coordinate: format!("@{}({}:)", definition.name, argument.name),
+                        reason,
+                        element: SchemaElement::DirectiveArgument(argument),
+                    });
+                }
+            }
+        }
+        result.sort_by(|l, r| l.coordinate.cmp(&r.coordinate));
+        result
+    }
+
+    // owned model, which can outlive Processed and be shared between threads
+    pub fn owned(&self) -> Arc<data::owned::Scheme> {
+        match self.owned {
+            Some(ref owned) => owned.clone(),
+            None => unreachable!("Processed scheme is returned by from() only with owned model"),
+        }
+    }
+
+    pub fn mutate(&self) -> Option<data::processed::Type> {
+        self.scheme
+            .mutate
+            .as_ref()
+            .and_then(|mutate| self.entry_point(mutate))
     }
 
     pub fn query(&self) -> data::processed::Type {
-        data::processed::Type::from(&self.scheme.query, &self.data_map)
+        let query = &self.scheme.query;
+        self.entry_point(query)
+            .unwrap_or_else(|| data::processed::Type::empty(query, &self.data_map))
+    }
+
+    // description of schema definition
+    pub fn scheme_description(&self) -> Option<&String> {
+        self.scheme.description.as_ref()
+    }
+
+    // directives applied to schema definition
+    pub fn scheme_directives(&self) -> data::processed::Directives {
+        data::processed::Directives::from(self.scheme.directives.iter())
+    }
+
+    pub fn subscription(&self) -> Option<data::processed::Type> {
+        self.scheme
+            .subscription
+            .as_ref()
+            .and_then(|subscription| self.entry_point(subscription))
+    }
+
+    // entry points are checked when scheme is processed, Processed cannot exist otherwise
+    fn entry_point<'a>(&'a self, name: &'a str) -> Option<data::processed::Type<'a>> {
+        data::processed::consistent(data::processed::Type::from(name, &self.data_map))
     }
 
-    pub fn from(unprocessed: Unprocessed) -> Result<Processed, Vec<String>> {
-        let result = Processed::build(unprocessed)?;
+    pub fn from(unprocessed: Unprocessed) -> Result<Processed, Vec<Diagnostic>> {
+        let mut result = Processed::build(unprocessed)?;
         let mut errors = vec![];
 
         // verify internal consistency
@@ -65,290 +405,1187 @@ impl Processed {
 
         // step 1: check types (as opposed to interfaces, enums and unions)
         for type_name in &result.types {
-            let gsdl_type = result.data_map.get(type_name).expect(&format!(
-                "Cannot find type {} in internal data map",
-                type_name
-            ));
-            match *gsdl_type {
-                unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
-                    assert_eq!(*type_name, gsdl_type.name);
+            match result.data_map.get(type_name) {
+                Some(&unprocessed::GsdlDataItem::Type(ref gsdl_type)) => {
+                    debug_assert_eq!(*type_name, gsdl_type.name);
 
                     // step 1.1: check referenced interfaces
-                    for interface_name in &gsdl_type.implements {
-                        let interface = result.data_map.get(interface_name);
-                        match interface {
-                            Some(interface) => match *interface {
-                                unprocessed::GsdlDataItem::Interface(ref interface) => {
-                                    assert_eq!(*interface_name, interface.name);
-                                    for interface_field in &interface.fields {
-                                        // using binary_search_by because binary_search_by_key
-                                        // requires key derivation function to return B, not &B
-                                        match gsdl_type
-                                            .fields
-                                            .binary_search_by(|x| x.name.cmp(&interface_field.name))
-                                        {
-                                            Ok(i) => {
-                                                let type_field = &gsdl_type.fields[i];
-                                                assert_eq!(interface_field.name, type_field.name);
-                                                if type_field != interface_field {
-                                                    errors.push(format!(
-                                                        "Type {} implements {}, \
-                                                         but field named {} is different: \
-                                                         {:?} in type, \
-                                                         {:?} in interface",
-                                                        gsdl_type.name,
-                                                        interface_name,
-                                                        interface_field.name,
-                                                        type_field,
-                                                        interface_field
-                                                    ))
-                                                }
-                                            }
-                                            Err(_) => errors.push(format!(
-                                                "Type {} implements {}, \
-                                                 but does not contain field named {}",
-                                                gsdl_type.name,
-                                                interface_name,
-                                                &interface_field.name
-                                            )),
-                                        }
-                                    }
-                                }
-                                _ => errors.push(format!(
-                                    "Type {} implements {}, but {} is not interface, \
-                                     but {:?} instead",
-                                    gsdl_type.name, interface_name, interface_name, interface
-                                )),
-                            },
-                            None => errors.push(format!(
-                                "Type {} implements {}, \
-                                 but {} is not defined",
-                                gsdl_type.name, interface_name, interface_name
-                            )),
-                        };
-                    }
+                    errors.append(&mut result.check_implements(
+                        &format!("Type {}", gsdl_type.name),
+                        &gsdl_type.name,
+                        gsdl_type.span,
+                        &gsdl_type.implements,
+                        &gsdl_type.fields,
+                        &gsdl_type.field_index,
+                    ));
 
                     // step 1.2: check that fields reference known data
                     for field in &gsdl_type.fields {
                         errors
                             .append(&mut result.check_field(field, &format!("Type {}", gsdl_type.name)));
                     }
+
+                    // step 1.3: check directives
+                    errors.append(&mut result.check_directives(
+                        &gsdl_type.directives,
+                        DirectiveLocation::OBJECT,
+                        &format!("Type {}", gsdl_type.name),
+                    ));
                 }
-                _ => panic!(format!(
+                other => debug_assert!(
+                    false,
                     "Type {} is not type but {:?} in internal data map",
-                    type_name, gsdl_type
-                )),
+                    type_name,
+                    other
+                ),
             }
         }
 
         // step 2: check interfaces
         for interface_name in &result.interfaces {
-            let interface = result.data_map.get(interface_name).expect(&format!(
-                "Cannot find interface {} in internal data map",
-                interface_name
-            ));
-            match *interface {
-                unprocessed::GsdlDataItem::Interface(ref interface) => {
-                    assert_eq!(*interface_name, interface.name);
+            match result.data_map.get(interface_name) {
+                Some(&unprocessed::GsdlDataItem::Interface(ref interface)) => {
+                    debug_assert_eq!(*interface_name, interface.name);
+                    let owner = format!("Interface {}", interface.name);
 
-                    // check that fields reference known data
+                    // step 2.1: check referenced interfaces, they cannot form a cycle
+                    if let Some(cycle) = result.implementation_cycle(&interface.name) {
+                        errors.push(
+                            Diagnostic::error(
+                                Code::InterfaceMismatch,
+                                format!(
+                                    "{} implements itself through {}",
+                                    owner,
+                                    cycle.join(" -> ")
+                                ),
+                            ).with_span(interface.span),
+                        );
+                    }
+                    errors.append(&mut result.check_implements(
+                        &owner,
+                        &interface.name,
+                        interface.span,
+                        &interface.implements,
+                        &interface.fields,
+                        &interface.field_index,
+                    ));
+
+                    // step 2.2: check that fields reference known data
                     for field in &interface.fields {
-                        errors.append(&mut result
-                            .check_field(field, &format!("Interface {}", interface.name)));
+                        errors.append(&mut result.check_field(field, &owner));
                     }
+
+                    // step 2.3: check directives
+                    errors.append(&mut result.check_directives(
+                        &interface.directives,
+                        DirectiveLocation::INTERFACE,
+                        &owner,
+                    ));
                 }
-                _ => panic!(format!(
+                other => debug_assert!(
+                    false,
                     "Interface {} is not interface but {:?} in internal data map",
-                    interface_name, interface
-                )),
+                    interface_name,
+                    other
+                ),
             }
         }
 
-        // step 3: check scheme entry points
-        // step 3.1: check query entry point
-        match result.data_map.get(&result.scheme.query) {
-            Some(gsdl_type) => match *gsdl_type {
-                unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
-                    assert_eq!(result.scheme.query, gsdl_type.name)
+        // step 3: check inputs
+        for input_name in &result.inputs {
+            match result.data_map.get(input_name) {
+                Some(&unprocessed::GsdlDataItem::Input(ref input)) => {
+                    debug_assert_eq!(*input_name, input.name);
+
+                    // check that fields reference known input data
+                    for field in &input.fields {
+                        errors.append(&mut result.check_argument(
+                            field,
+                            DirectiveLocation::INPUT_FIELD_DEFINITION,
+                            &format!("Input {} field {}", input.name, field.name),
+                        ));
+                    }
+
+                    errors.append(&mut result.check_directives(
+                        &input.directives,
+                        DirectiveLocation::INPUT_OBJECT,
+                        &format!("Input {}", input.name),
+                    ));
                 }
-                _ => errors.push(format!(
-                    "Scheme query entry point {} is not a type but {:?} instead",
-                    result.scheme.query, gsdl_type
-                )),
-            },
-            None => errors.push(format!(
-                "Scheme query entry point {} type is not defined",
-                result.scheme.query
-            )),
+                other => debug_assert!(
+                    false,
+                    "Input {} is not input but {:?} in internal data map",
+                    input_name,
+                    other
+                ),
+            }
+        }
+
+        // step 4: check scheme entry points
+        if let Some(error) = result.check_entry_point("query", &result.scheme.query) {
+            errors.push(error);
         }
-        // step 3.2: check mutate entry point
         if let Some(ref mutate) = result.scheme.mutate {
-            match result.data_map.get(mutate) {
-                Some(gsdl_type) => match *gsdl_type {
-                    unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
-                        assert_eq!(result.scheme.query, gsdl_type.name)
-                    }
-                    _ => errors.push(format!(
-                        "Scheme mutate entry point {} is not a type but {:?} instead",
-                        result.scheme.query, gsdl_type
-                    )),
-                },
-                None => errors.push(format!(
-                    "Scheme mutate entry point {} type is not defined",
-                    result.scheme.query
-                )),
+            if let Some(error) = result.check_entry_point("mutation", mutate) {
+                errors.push(error);
             }
         }
+        if let Some(ref subscription) = result.scheme.subscription {
+            if let Some(error) = result.check_entry_point("subscription", subscription) {
+                errors.push(error);
+            }
+        }
+        errors.append(&mut result.check_directives(
+            &result.scheme.directives,
+            DirectiveLocation::SCHEMA,
+            "Scheme",
+        ));
 
-        if errors.is_empty() {
-            Ok(result)
-        } else {
-            Err(errors)
+        // step 5: check union members and directives of enums, unions and scalars
+        for gsdl_enum in result.enums().into_iter() {
+            if let Some(&unprocessed::GsdlDataItem::Enum(ref gsdl_enum)) =
+                result.data_map.get(gsdl_enum.name)
+            {
+                let owner = format!("Enum {}", gsdl_enum.name);
+                errors.append(&mut result.check_directives(
+                    &gsdl_enum.directives,
+                    DirectiveLocation::ENUM,
+                    &owner,
+                ));
+                for value in &gsdl_enum.values {
+                    errors.append(&mut result.check_directives(
+                        &value.directives,
+                        DirectiveLocation::ENUM_VALUE,
+                        &format!("{} value {}", owner, value.name),
+                    ));
+                }
+            }
+        }
+        for union_name in &result.unions {
+            if let Some(&unprocessed::GsdlDataItem::Union(ref union)) =
+                result.data_map.get(union_name)
+            {
+                let owner = format!("Union {}", union.name);
+                for member in &union.members {
+                    errors.append(&mut result.check_union_member(member, union.span, &owner));
+                }
+                errors.append(&mut result.check_directives(
+                    &union.directives,
+                    DirectiveLocation::UNION,
+                    &owner,
+                ));
+            }
+        }
+        for scalar_name in &result.scalars {
+            if let Some(&unprocessed::GsdlDataItem::Scalar(ref scalar)) =
+                result.data_map.get(scalar_name)
+            {
+                errors.append(&mut result.check_directives(
+                    &scalar.directives,
+                    DirectiveLocation::SCALAR,
+                    &format!("Scalar {}", scalar.name),
+                ));
+            }
+        }
+
+        // step 6: check directive definitions arguments
+        for directive_definition in &result.directive_definitions {
+            for argument in &directive_definition.arguments {
+                errors.append(&mut result.check_argument(
+                    argument,
+                    DirectiveLocation::ARGUMENT_DEFINITION,
+                    &format!(
+                        "Directive @{} argument {}",
+                        directive_definition.name, argument.name
+                    ),
+                ));
+            }
+        }
+
+        // step 7: check names reserved by the spec
+        errors.append(&mut result.check_names());
+
+        // step 8: build owned model once all references are known to be valid
+        if !errors.is_empty() {
+            return Err(errors);
         }
+        match data::owned::Scheme::from(
+            &result.data_map,
+            &result.directive_definitions,
+            &result.scheme,
+        ) {
+            Ok(owned) => {
+                result.owned = Some(Arc::new(owned));
+                Ok(result)
+            }
+            Err(error) => Err(vec![Diagnostic::error(
+                Code::InvalidSchema,
+                format!("Internal data map is inconsistent: {}", error),
+            )]),
+        }
+    }
+
+    // names starting with __ are reserved for introspection, enum values cannot be true, false or null
+    fn check_names(&self) -> Vec<Diagnostic> {
+        let mut errors = vec![];
+        for name in self.types.iter().chain(&self.interfaces) {
+            match self.data_map.get(name) {
+                Some(&unprocessed::GsdlDataItem::Type(ref gsdl_type)) => {
+                    let owner = format!("Type {}", gsdl_type.name);
+                    errors.extend(reserved_name(&owner, &gsdl_type.name, gsdl_type.span));
+                    errors.append(&mut reserved_field_names(&owner, &gsdl_type.fields));
+                }
+                Some(&unprocessed::GsdlDataItem::Interface(ref interface)) => {
+                    let owner = format!("Interface {}", interface.name);
+                    errors.extend(reserved_name(&owner, &interface.name, interface.span));
+                    errors.append(&mut reserved_field_names(&owner, &interface.fields));
+                }
+                _ => (),
+            }
+        }
+
+        for name in &self.enums {
+            if let Some(&unprocessed::GsdlDataItem::Enum(ref gsdl_enum)) = self.data_map.get(name) {
+                let owner = format!("Enum {}", gsdl_enum.name);
+                errors.extend(reserved_name(&owner, &gsdl_enum.name, gsdl_enum.span));
+                for value in &gsdl_enum.values {
+                    let value_owner = format!("{} value {}", owner, value.name);
+                    errors.extend(reserved_name(&value_owner, &value.name, value.span));
+                    if value.name == "true" || value.name == "false" || value.name == "null" {
+                        errors.push(
+                            Diagnostic::error(
+                                Code::ReservedName,
+                                format!(
+                                    "{} is not allowed, enum values cannot be true, false or null",
+                                    value_owner
+                                ),
+                            ).with_span(value.span),
+                        );
+                    }
+                }
+            }
+        }
+        for name in &self.inputs {
+            if let Some(&unprocessed::GsdlDataItem::Input(ref input)) = self.data_map.get(name) {
+                let owner = format!("Input {}", input.name);
+                errors.extend(reserved_name(&owner, &input.name, input.span));
+                for field in &input.fields {
+                    errors.extend(reserved_name(
+                        &format!("{} field {}", owner, field.name),
+                        &field.name,
+                        field.span,
+                    ));
+                }
+            }
+        }
+        for name in self.scalars.iter().chain(&self.unions) {
+            match self.data_map.get(name) {
+                Some(&unprocessed::GsdlDataItem::Scalar(ref scalar)) => errors.extend(
+                    reserved_name(&format!("Scalar {}", scalar.name), &scalar.name, scalar.span),
+                ),
+                Some(&unprocessed::GsdlDataItem::Union(ref union)) => errors.extend(
+                    reserved_name(&format!("Union {}", union.name), &union.name, union.span),
+                ),
+                _ => (),
+            }
+        }
+        for definition in &self.directive_definitions {
+            let owner = format!("Directive @{}", definition.name);
+            errors.extend(reserved_name(&owner, &definition.name, definition.span));
+            for argument in &definition.arguments {
+                errors.extend(reserved_name(
+                    &format!("{} argument {}", owner, argument.name),
+                    &argument.name,
+                    argument.span,
+                ));
+            }
+        }
+        errors
+    }
+
+    // check that scheme entry point references a type
+    fn check_entry_point(&self, kind: &str, name: &str) -> Option<Diagnostic> {
+        let error = match self.data_map.get(name) {
+            Some(gsdl_type) => match *gsdl_type {
+                unprocessed::GsdlDataItem::Type(ref gsdl_type) => {
+                    debug_assert_eq!(name, gsdl_type.name);
+                    return None;
+                }
+                _ => Diagnostic::error(
+                    Code::WrongKind,
+                    format!(
+                        "Scheme {} entry point {} is not a type but {:?} instead",
+                        kind, name, gsdl_type
+                    ),
+                ),
+            },
+            None => Diagnostic::error(
+                Code::UndefinedReference,
+                format!("Scheme {} entry point {} type is not defined", kind, name),
+            ),
+        };
+
+        Some(match self.scheme.span {
+            Some(span) => error.with_span(span),
+            None => error,
+        })
     }
 
     // check that field references known data
-    fn check_field(&self, field: &unprocessed::Field, parent: &str) -> Vec<String> {
+    fn check_field(&self, field: &unprocessed::Field, parent: &str) -> Vec<Diagnostic> {
         let mut errors = vec![];
 
         // step 1: Check field return type
-        let gsdl_field_type = self.data_map.get(&field.field_type.inner.name);
+        let field_type_name = field.field_type.name();
+        let gsdl_field_type = self.data_map.get(field_type_name);
         match gsdl_field_type {
-            Some(gsdl_item) => assert_eq!(*field.field_type.inner.name, *gsdl_item.name()),
-            None => errors.push(format!(
-                "{} field {} uses return type {}, but {} is not defined",
-                parent, field.name, field.field_type.inner.name, field.field_type.inner.name
-            )),
+            Some(gsdl_item) => {
+                debug_assert_eq!(*field_type_name, *gsdl_item.name());
+                if !gsdl_item.is_output_type() {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::WrongKind,
+                            format!(
+                                "{} field {} uses return type {}, but {} is {} and cannot be returned",
+                                parent,
+                                field.name,
+                                field_type_name,
+                                field_type_name,
+                                gsdl_item.kind()
+                            ),
+                        ).with_span(field.span),
+                    )
+                }
+            }
+            None => errors.push(
+                Diagnostic::error(
+                    Code::UndefinedReference,
+                    format!(
+                        "{} field {} uses return type {}, but {} is not defined",
+                        parent, field.name, field_type_name, field_type_name
+                    ),
+                ).with_span(field.span),
+            ),
         };
 
         // step 2: Check field arguments
         for argument in &field.arguments {
-            let gsdl_argument_type = self.data_map.get(&argument.argument_type.inner.name);
-            match gsdl_argument_type {
-                Some(gsdl_item) => {
-                    assert_eq!(*argument.argument_type.inner.name, *gsdl_item.name())
+            errors.append(&mut self.check_argument(
+                argument,
+                DirectiveLocation::ARGUMENT_DEFINITION,
+                &format!("{} field {} argument {}", parent, field.name, argument.name),
+            ));
+        }
+
+        // step 3: Check field directives
+        errors.append(&mut self.check_directives(
+            &field.directives,
+            DirectiveLocation::FIELD_DEFINITION,
+            &format!("{} field {}", parent, field.name),
+        ));
+
+        errors
+    }
+
+    // union members have to be object types
+    fn check_union_member(&self, member: &str, span: Span, owner: &str) -> Vec<Diagnostic> {
+        match self.data_map.get(member) {
+            Some(&unprocessed::GsdlDataItem::Type(_)) => vec![],
+            Some(other) => vec![
+                Diagnostic::error(
+                    Code::WrongKind,
+                    format!(
+                        "{} includes {}, but {} is {} and only types can be union members",
+                        owner,
+                        member,
+                        member,
+                        other.kind()
+                    ),
+                ).with_span(span),
+            ],
+            None => vec![
+                Diagnostic::error(
+                    Code::UndefinedReference,
+                    format!("{} includes {}, but {} is not defined", owner, member, member),
+                ).with_span(span),
+            ],
+        }
+    }
+
+    // check that argument or input field references known input data
+    fn check_argument(
+        &self,
+        argument: &unprocessed::Argument,
+        location: DirectiveLocation,
+        owner: &str,
+    ) -> Vec<Diagnostic> {
+        let mut errors = vec![];
+
+        let argument_type_name = argument.argument_type.name();
+        match self.data_map.get(argument_type_name) {
+            Some(gsdl_item) => {
+                debug_assert_eq!(*argument_type_name, *gsdl_item.name());
+                if !gsdl_item.is_input_type() {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::WrongKind,
+                            format!(
+                                "{} references type {}, but {} is {} and cannot be used as input",
+                                owner,
+                                argument_type_name,
+                                argument_type_name,
+                                gsdl_item.kind()
+                            ),
+                        ).with_span(argument.span),
+                    )
+                } else if let Some(ref default) = argument.default {
+                    if let Err(message) = self.check_value(default, &argument.argument_type) {
+                        errors.push(
+                            Diagnostic::error(
+                                Code::InvalidValue,
+                                format!("{} has invalid default value: {}", owner, message),
+                            ).with_span(argument.span),
+                        )
+                    }
+                }
+            }
+            None => errors.push(
+                Diagnostic::error(
+                    Code::UndefinedReference,
+                    format!(
+                        "{} references type {}, but {} is not defined",
+                        owner, argument_type_name, argument_type_name
+                    ),
+                ).with_span(argument.span),
+            ),
+        };
+
+        errors.append(&mut self.check_directives(&argument.directives, location, owner));
+
+        // required arguments and input fields have to be provided, so they cannot be deprecated
+        let is_required = !argument.argument_type.nullable && argument.default.is_none();
+        if let Some(deprecated) = argument.directives.iter().find(|d| d.name == "deprecated") {
+            if is_required {
+                errors.push(
+                    Diagnostic::error(
+                        Code::InvalidDirective,
+                        format!("{} is required and cannot be deprecated", owner),
+                    ).with_span(deprecated.span),
+                );
+            }
+        }
+
+        errors
+    }
+
+    // check interfaces implemented by type or interface
+    fn check_implements(
+        &self,
+        owner: &str,
+        name: &str,
+        span: Span,
+        implements: &[String],
+        fields: &[unprocessed::Field],
+        field_index: &unprocessed::NameIndex,
+    ) -> Vec<Diagnostic> {
+        let mut errors = vec![];
+        for interface_name in implements {
+            let interface = match self.data_map.get(interface_name) {
+                Some(&unprocessed::GsdlDataItem::Interface(ref interface)) => interface,
+                Some(other) => {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::WrongKind,
+                            format!(
+                                "{} implements {}, but {} is not interface, but {} instead",
+                                owner,
+                                interface_name,
+                                interface_name,
+                                other.kind()
+                            ),
+                        ).with_span(span),
+                    );
+                    continue;
+                }
+                None => {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::UndefinedReference,
+                            format!(
+                                "{} implements {}, but {} is not defined",
+                                owner, interface_name, interface_name
+                            ),
+                        ).with_span(span),
+                    );
+                    continue;
+                }
+            };
+            debug_assert_eq!(*interface_name, interface.name);
+
+            // step 1: interfaces implemented by interface have to be listed explicitly,
+            // name itself is skipped as it means cycle, which is reported separately
+            for transitive in &interface.implements {
+                if transitive != name && !implements.contains(transitive) {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::InterfaceMismatch,
+                            format!(
+                                "{} implements {}, but not {}, which {} implements",
+                                owner, interface_name, transitive, interface_name
+                            ),
+                        ).with_span(span)
+                            .with_label(interface.span, "interface defined here"),
+                    );
                 }
-                None => errors.push(format!(
-                    "{} field {}  argument {} references type {}, but {} is not defined",
-                    parent,
-                    field.name,
-                    argument.name,
-                    argument.argument_type.inner.name,
-                    argument.argument_type.inner.name
+            }
+
+            // step 2: every interface field has to be implemented
+            for interface_field in &interface.fields {
+                match field_index.find(fields, &interface_field.name) {
+                    Some(field) => {
+                        debug_assert_eq!(interface_field.name, field.name);
+                        errors.append(&mut self.check_implementation(
+                            field,
+                            interface_field,
+                            &format!("{} implements {}", owner, interface_name),
+                        ));
+                    }
+                    None => errors.push(
+                        Diagnostic::error(
+                            Code::InterfaceMismatch,
+                            format!(
+                                "{} implements {}, but does not contain field named {}",
+                                owner, interface_name, interface_field.name
+                            ),
+                        ).with_span(span)
+                            .with_label(interface_field.span, "interface field defined here"),
+                    ),
+                }
+            }
+        }
+        errors
+    }
+
+    // chain of interface names leading from interface back to itself, e.g. [A, B, A]
+    fn implementation_cycle(&self, name: &str) -> Option<Vec<String>> {
+        let mut path = vec![String::from(name)];
+        if self.find_implementation_path(name, name, &mut path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    // depth first search, interfaces already on path are not visited again
+    fn find_implementation_path(
+        &self,
+        current: &str,
+        target: &str,
+        path: &mut Vec<String>,
+    ) -> bool {
+        let interface = match self.data_map.get(current) {
+            Some(&unprocessed::GsdlDataItem::Interface(ref interface)) => interface,
+            _ => return false,
+        };
+        for next in &interface.implements {
+            path.push(next.clone());
+            if next == target {
+                return true;
+            }
+            if !path[..path.len() - 1].contains(next)
+                && self.find_implementation_path(next, target, path)
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    // IsValidImplementation from the spec: field type may be covariant, interface arguments are
+    // required with the same types, additional arguments must be optional
+    fn check_implementation(
+        &self,
+        field: &unprocessed::Field,
+        interface_field: &unprocessed::Field,
+        owner: &str,
+    ) -> Vec<Diagnostic> {
+        let mut errors = vec![];
+        let error = |message: String, span: Span, interface_span: Span, label: &str| {
+            Diagnostic::error(Code::InterfaceMismatch, message)
+                .with_span(span)
+                .with_label(interface_span, label)
+        };
+
+        // step 1: field type must be valid implementation of interface field type
+        if !self.is_valid_implementation_type(&field.field_type, &interface_field.field_type) {
+            errors.push(error(
+                format!(
+                    "{}, but field {} returns {}, which is not compatible with {} in interface",
+                    owner, field.name, field.field_type, interface_field.field_type
+                ),
+                field.span,
+                interface_field.span,
+                "interface field defined here",
+            ));
+        }
+
+        // step 2: arguments of interface field must be present with the same type
+        for interface_argument in &interface_field.arguments {
+            match field.argument_index.find(&field.arguments, &interface_argument.name) {
+                Some(argument) => if argument.argument_type != interface_argument.argument_type {
+                    errors.push(error(
+                        format!(
+                            "{}, but field {} argument {} has type {}, while interface has {}",
+                            owner,
+                            field.name,
+                            argument.name,
+                            argument.argument_type,
+                            interface_argument.argument_type
+                        ),
+                        argument.span,
+                        interface_argument.span,
+                        "interface argument defined here",
+                    ));
+                },
+                None => errors.push(error(
+                    format!(
+                        "{}, but field {} does not have argument {} of interface",
+                        owner, field.name, interface_argument.name
+                    ),
+                    field.span,
+                    interface_argument.span,
+                    "interface argument defined here",
                 )),
+            }
+        }
+
+        // step 3: additional arguments cannot be required
+        for argument in &field.arguments {
+            let is_additional = interface_field
+                .argument_index
+                .find(&interface_field.arguments, &argument.name)
+                .is_none();
+            if is_additional && !argument.argument_type.nullable && argument.default.is_none() {
+                errors.push(error(
+                    format!(
+                        "{}, but field {} has additional required argument {}: {}",
+                        owner, field.name, argument.name, argument.argument_type
+                    ),
+                    argument.span,
+                    interface_field.span,
+                    "interface field defined here",
+                ));
+            }
+        }
+
+        errors
+    }
+
+    // IsValidImplementationFieldType from the spec
+    fn is_valid_implementation_type(
+        &self,
+        field_type: &unprocessed::OuterType,
+        interface_type: &unprocessed::OuterType,
+    ) -> bool {
+        use graphql::data::unprocessed::InnerType;
+
+        // non-null type can implement nullable one, but not the other way around
+        if field_type.nullable && !interface_type.nullable {
+            return false;
+        }
+        match (&field_type.inner, &interface_type.inner) {
+            (&InnerType::List(ref item), &InnerType::List(ref interface_item)) => {
+                self.is_valid_implementation_type(item, interface_item)
+            }
+            (&InnerType::Named(ref name), &InnerType::Named(ref interface_name)) => {
+                self.is_subtype(name, interface_name)
+            }
+            _ => false,
+        }
+    }
+
+    // IsSubType from the spec: the same type, member of union or implementation of interface
+    fn is_subtype(&self, name: &str, abstract_name: &str) -> bool {
+        use graphql::data::unprocessed::GsdlDataItem;
+
+        if name == abstract_name {
+            return true;
+        }
+        match self.data_map.get(abstract_name) {
+            Some(&GsdlDataItem::Union(ref union)) => union.members.iter().any(|m| m == name),
+            Some(&GsdlDataItem::Interface(_)) => match self.data_map.get(name) {
+                Some(&GsdlDataItem::Type(ref gsdl_type)) => {
+                    gsdl_type.implements.iter().any(|i| i == abstract_name)
+                }
+                Some(&GsdlDataItem::Interface(ref interface)) => {
+                    interface.implements.iter().any(|i| i == abstract_name)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn find_directive_definition(&self, name: &str) -> Option<&unprocessed::DirectiveDefinition> {
+        self.directive_definitions
+            .binary_search_by(|d| d.name.as_str().cmp(name))
+            .ok()
+            .map(|i| &self.directive_definitions[i])
+    }
+
+    // check directive applications against their definitions
+    fn check_directives(
+        &self,
+        directives: &[unprocessed::Directive],
+        location: DirectiveLocation,
+        owner: &str,
+    ) -> Vec<Diagnostic> {
+        let mut errors = vec![];
+
+        for (i, directive) in directives.iter().enumerate() {
+            let definition = match self.find_directive_definition(&directive.name) {
+                Some(definition) => definition,
+                None => {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::UndefinedReference,
+                            format!(
+                                "{} uses directive @{}, but @{} is not defined",
+                                owner, directive.name, directive.name
+                            ),
+                        ).with_span(directive.span),
+                    );
+                    continue;
+                }
             };
+
+            // step 1: check location
+            let error = |message| Diagnostic::error(Code::InvalidDirective, message).with_span(directive.span);
+            if !definition.locations.contains(&location) {
+                errors.push(error(format!(
+                    "{} uses directive @{}, but @{} is not allowed on {}",
+                    owner,
+                    directive.name,
+                    directive.name,
+                    DirectiveLocation::name(location)
+                )))
+            }
+
+            // step 2: check repetition, reported at first usage only
+            if !definition.repeatable && directives[..i].iter().all(|d| d.name != directive.name) {
+                let repeated: Vec<&unprocessed::Directive> = directives[i + 1..]
+                    .iter()
+                    .filter(|d| d.name == directive.name)
+                    .collect();
+                if !repeated.is_empty() {
+                    errors.push(repeated.iter().fold(
+                        error(format!(
+                            "{} uses directive @{} {} times, but @{} is not repeatable",
+                            owner,
+                            directive.name,
+                            repeated.len() + 1,
+                            directive.name
+                        )),
+                        |error, d| error.with_label(d.span, "used again here"),
+                    ))
+                }
+            }
+
+            // step 3: check passed arguments
+            for (j, &(ref argument_name, ref value)) in directive.arguments.iter().enumerate() {
+                if directive.arguments[..j]
+                    .iter()
+                    .any(|a| a.0 == *argument_name)
+                {
+                    errors.push(error(format!(
+                        "{} uses directive @{} with duplicate argument {}",
+                        owner, directive.name, argument_name
+                    )));
+                    continue;
+                }
+                match definition
+                    .argument_index
+                    .find(&definition.arguments, argument_name)
+                {
+                    Some(argument) => {
+                        if let Err(message) = self.check_value(value, &argument.argument_type) {
+                            errors.push(error(format!(
+                                "{} uses directive @{} with invalid argument {}: {}",
+                                owner, directive.name, argument_name, message
+                            )))
+                        }
+                    }
+                    None => errors.push(error(format!(
+                        "{} uses directive @{} with argument {}, but @{} has no such argument",
+                        owner, directive.name, argument_name, directive.name
+                    ))),
+                }
+            }
+
+            // step 4: check required arguments are present
+            for argument in &definition.arguments {
+                if !argument.argument_type.nullable && argument.default.is_none()
+                    && directive.argument(&argument.name).is_none()
+                {
+                    errors.push(error(format!(
+                        "{} uses directive @{} without required argument {}",
+                        owner, directive.name, argument.name
+                    )))
+                }
+            }
         }
 
         errors
     }
 
+    // check that value literal can be coerced to given input type
+    fn check_value(
+        &self,
+        value: &unprocessed::Value,
+        value_type: &unprocessed::OuterType,
+    ) -> Result<(), String> {
+        use graphql::data::unprocessed::{GsdlDataItem, InnerType, Scalar, Value};
+
+        if let Value::Null = *value {
+            return if value_type.nullable {
+                Ok(())
+            } else {
+                Err(format!("expected {}, found null", value_type))
+            };
+        }
+
+        let valid = match value_type.inner {
+            InnerType::List(ref item_type) => {
+                return match *value {
+                    Value::List(ref items) => {
+                        for item in items {
+                            self.check_value(item, item_type)?
+                        }
+                        Ok(())
+                    }
+                    // single value is coerced to a list of one item
+                    _ => self.check_value(value, item_type),
+                }
+            }
+            InnerType::Named(ref name) => match self.data_map.get(name) {
+                Some(&GsdlDataItem::Builtin(scalar)) => match (scalar, value) {
+                    (Scalar::Int, &Value::Int(value)) => {
+                        value >= i64::from(i32::MIN) && value <= i64::from(i32::MAX)
+                    }
+                    (Scalar::Float, &Value::Int(_))
+                    | (Scalar::Float, &Value::Float(_))
+                    | (Scalar::String, &Value::String(_))
+                    | (Scalar::Boolean, &Value::Boolean(_))
+                    | (Scalar::ID, &Value::String(_))
+                    | (Scalar::ID, &Value::Int(_)) => true,
+                    _ => false,
+                },
+                Some(&GsdlDataItem::Enum(ref gsdl_enum)) => match *value {
+                    Value::Enum(ref value) => gsdl_enum
+                        .value_index
+                        .find(&gsdl_enum.values, value)
+                        .is_some(),
+                    _ => false,
+                },
+                Some(&GsdlDataItem::Input(ref input)) => {
+                    return match *value {
+                        Value::Object(ref fields) => self.check_object_value(input, fields),
+                        _ => Err(format!("expected {}, found {}", value_type, value)),
+                    }
+                }
+                // custom scalars accept any literal, non-input and undefined types are reported elsewhere
+                _ => true,
+            },
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found {}", value_type, value))
+        }
+    }
+
+    fn check_object_value(
+        &self,
+        input: &unprocessed::Input,
+        fields: &[(String, unprocessed::Value)],
+    ) -> Result<(), String> {
+        for (i, &(ref name, ref value)) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|f| f.0 == *name) {
+                return Err(format!("duplicate field {} of {}", name, input.name));
+            }
+            match input.field_index.find(&input.fields, name) {
+                Some(field) => self.check_value(value, &field.argument_type)
+                    .map_err(|error| format!("field {} of {}: {}", name, input.name, error))?,
+                None => return Err(format!("{} has no field {}", input.name, name)),
+            }
+        }
+        for field in &input.fields {
+            if !field.argument_type.nullable && field.default.is_none()
+                && fields.iter().all(|f| f.0 != field.name)
+            {
+                return Err(format!(
+                    "missing required field {} of {}",
+                    field.name, input.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
     // internal function to build Processed with minimal checks
-    fn build(unprocessed: Unprocessed) -> Result<Processed, Vec<String>> {
+    fn build(unprocessed: Unprocessed) -> Result<Processed, Vec<Diagnostic>> {
         // step 1
         // init internal structure holding all possible data
         let mut data_map = HashMap::with_capacity(
-            unprocessed::Scalar::count() + unprocessed.enums.len() + unprocessed.interfaces.len()
+            unprocessed::Scalar::count() + unprocessed.enums.len() + unprocessed.inputs.len()
+                + unprocessed.interfaces.len() + unprocessed.scalars.len()
                 + unprocessed.types.len() + unprocessed.unions.len(),
         );
 
         // step 2: add builtin scalars
+        // data map is still empty and builtin scalars have distinct names
         for &(ref scalar, scalar_name) in unprocessed::Scalar::iter() {
-            match data_map.insert(
+            let previous = data_map.insert(
                 String::from(scalar_name),
                 unprocessed::GsdlDataItem::Builtin(*scalar),
-            ) {
-                None => (),
-                Some(gsdl_type) => {
-                    panic!(format!("Builtin type {:?}  is defined twice", gsdl_type))
-                }
-            }
+            );
+            debug_assert!(previous.is_none(), "Builtin type {:?} is defined twice", previous);
         }
 
         let mut errors = vec![];
 
-        // step 3: add enums
+        // step 3: add builtin and user defined directive definitions
+        // builtin sources are constants parsed by every test, so parse errors are unreachable
+        let mut directive_definitions: Vec<unprocessed::DirectiveDefinition> = BUILTIN_DIRECTIVES
+            .iter()
+            .map(|source| {
+                parse_DirectiveDefinition(Lexer::new(source)).unwrap_or_else(|error| {
+                    panic!("Builtin directive {} cannot be parsed: {:?}", source, error)
+                })
+            })
+            .collect();
+        let builtin_count = directive_definitions.len();
+        directive_definitions.extend(unprocessed.directive_definitions);
+        // spans of builtin directives point into BUILTIN_DIRECTIVES, not the source
+        let builtin_names: Vec<String> = directive_definitions[..builtin_count]
+            .iter()
+            .map(|d| d.name.to_owned())
+            .collect();
+        // stable sort, so builtin definition precedes user redefinition
+        directive_definitions.sort_by(|l, r| l.name.cmp(&r.name));
+        for (i, directive_definition) in directive_definitions.iter().enumerate().skip(1) {
+            let previous = &directive_definitions[i - 1];
+            if previous.name == directive_definition.name {
+                let message = format!("Directive @{} is already defined", directive_definition.name);
+                errors.push(if builtin_names.contains(&previous.name) {
+                    Diagnostic::error(Code::DuplicateDefinition, message)
+                        .with_span(directive_definition.span)
+                } else {
+                    Diagnostic::duplicate(message, previous.span, directive_definition.span)
+                })
+            }
+        }
+
+        // step 4: add enums
         let mut enums = Vec::with_capacity(unprocessed.enums.len());
         for gsdl_enum in unprocessed.enums {
             let enum_name = gsdl_enum.name.to_owned();
             let key = gsdl_enum.name.to_owned();
+            let span = gsdl_enum.span;
             match data_map.insert(key, unprocessed::GsdlDataItem::Enum(gsdl_enum)) {
                 None => (),
-                Some(gsdl_type) => errors.push(format!(
-                    "Enum {} is already defined as {:?}",
-                    enum_name, gsdl_type
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Enum {} is already defined as {:?}", enum_name, gsdl_type),
+                    &gsdl_type,
+                    span,
                 )),
             }
             enums.push(enum_name);
         }
         enums.sort_unstable();
 
-        // step 4: add interfaces
+        // step 5: add inputs
+        let mut inputs = Vec::with_capacity(unprocessed.inputs.len());
+        for input in unprocessed.inputs {
+            let input_name = input.name.to_owned();
+            let key = input.name.to_owned();
+            let span = input.span;
+            match data_map.insert(key, unprocessed::GsdlDataItem::Input(input)) {
+                None => (),
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Input {} is already defined as {:?}", input_name, gsdl_type),
+                    &gsdl_type,
+                    span,
+                )),
+            }
+            inputs.push(input_name);
+        }
+        inputs.sort_unstable();
+
+        // step 6: add interfaces
         let mut interfaces = Vec::with_capacity(unprocessed.interfaces.len());
         for interface in unprocessed.interfaces {
             let interface_name = interface.name.to_owned();
             let key = interface.name.to_owned();
+            let span = interface.span;
             match data_map.insert(key, unprocessed::GsdlDataItem::Interface(interface)) {
                 None => (),
-                Some(gsdl_type) => errors.push(format!(
-                    "Interface {} is already defined as {:?}",
-                    interface_name, gsdl_type
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Interface {} is already defined as {:?}", interface_name, gsdl_type),
+                    &gsdl_type,
+                    span,
                 )),
             }
             interfaces.push(interface_name);
         }
         interfaces.sort_unstable();
 
-        // step 5: add types
+        // step 7: add custom scalars
+        let mut scalars = Vec::with_capacity(unprocessed.scalars.len());
+        for scalar in unprocessed.scalars {
+            let scalar_name = scalar.name.to_owned();
+            let key = scalar.name.to_owned();
+            let span = scalar.span;
+            match data_map.insert(key, unprocessed::GsdlDataItem::Scalar(scalar)) {
+                None => (),
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Scalar {} is already defined as {:?}", scalar_name, gsdl_type),
+                    &gsdl_type,
+                    span,
+                )),
+            }
+            scalars.push(scalar_name);
+        }
+        scalars.sort_unstable();
+
+        // step 8: add types
         let mut types = Vec::with_capacity(unprocessed.types.len());
         for gsdl_type in unprocessed.types {
             let type_name = gsdl_type.name.to_owned();
             let key = gsdl_type.name.to_owned();
+            let span = gsdl_type.span;
             match data_map.insert(key, unprocessed::GsdlDataItem::Type(gsdl_type)) {
                 None => (),
-                Some(gsdl_type) => errors.push(format!(
-                    "Type {} is already defined as {:?}",
-                    type_name, gsdl_type
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Type {} is already defined as {:?}", type_name, gsdl_type),
+                    &gsdl_type,
+                    span,
                 )),
             };
             types.push(type_name);
         }
         types.sort_unstable();
 
-        // step 6: add unions
+        // step 9: add unions
         let mut unions = vec![];
         for union in unprocessed.unions {
             let union_name = union.name.to_owned();
             let key = union.name.to_owned();
+            let span = union.span;
             match data_map.insert(key, unprocessed::GsdlDataItem::Union(union)) {
                 None => (),
-                Some(gsdl_type) => errors.push(format!(
-                    "Union {} is already defined as {:?}",
-                    union_name, gsdl_type
+                Some(gsdl_type) => errors.push(already_defined(
+                    format!("Union {} is already defined as {:?}", union_name, gsdl_type),
+                    &gsdl_type,
+                    span,
                 )),
             };
             unions.push(union_name);
         }
         unions.sort_unstable();
 
-        // step 7: init scheme entry points
-        let scheme = Scheme {
-            query: match unprocessed.query {
-                Some(query) => query,
-                None => {
-                    errors.push(String::from("No scheme query entry point defined"));
-                    String::from("")
+        // step 10: add introspection types, user definitions of their names are reported as
+        // reserved names by check_names()
+        // as builtin directives, introspection sources are constants parsed by every test
+        let mut introspection_types = vec![];
+        let introspection_items = INTROSPECTION_TYPES
+            .iter()
+            .map(|source| {
+                parse_Type(Lexer::new(source))
+                    .map(unprocessed::GsdlDataItem::Type)
+                    .unwrap_or_else(|error| {
+                        panic!("Introspection type {} cannot be parsed: {:?}", source, error)
+                    })
+            })
+            .chain(INTROSPECTION_ENUMS.iter().map(|source| {
+                parse_Enum(Lexer::new(source))
+                    .map(unprocessed::GsdlDataItem::Enum)
+                    .unwrap_or_else(|error| {
+                        panic!("Introspection enum {} cannot be parsed: {:?}", source, error)
+                    })
+            }));
+        for item in introspection_items {
+            let name = item.name().to_owned();
+            data_map.entry(name.to_owned()).or_insert(item);
+            introspection_types.push(name);
+        }
+        introspection_types.sort_unstable();
+        let meta_field = |source: &str| {
+            parse_Field(Lexer::new(source)).unwrap_or_else(|error| {
+                panic!("Meta field {} cannot be parsed: {:?}", source, error)
+            })
+        };
+
+        // step 11: init scheme entry points
+        // without explicit schema definition, types named Query, Mutation and Subscription are used
+        let scheme = if unprocessed.scheme_entry_points_encountered {
+            Scheme {
+                query: match unprocessed.query {
+                    Some(query) => query,
+                    None => {
+                        errors.push(
+                            Diagnostic::error(
+                                Code::InvalidSchema,
+                                String::from("No scheme query entry point defined"),
+                            ).with_span(unprocessed.scheme_span.unwrap_or_default()),
+                        );
+                        String::from("")
+                    }
+                },
+                mutate: unprocessed.mutate,
+                subscription: unprocessed.subscription,
+                description: unprocessed.scheme_description,
+                span: unprocessed.scheme_span,
+                directives: unprocessed.scheme_directives,
+            }
+        } else {
+            let implicit = |name: &str| {
+                if data_map.contains_key(name) {
+                    Some(String::from(name))
+                } else {
+                    None
                 }
-            },
-            mutate: unprocessed.mutate,
+            };
+            Scheme {
+                query: String::from("Query"),
+                mutate: implicit("Mutation"),
+                subscription: implicit("Subscription"),
+                description: unprocessed.scheme_description,
+                span: None,
+                directives: unprocessed.scheme_directives,
+            }
         };
 
         if errors.is_empty() {
             Ok(Processed {
                 data_map,
+                directive_definitions,
                 enums,
+                inputs,
                 interfaces,
+                introspection_types,
+                // set in from() after checks
+                owned: None,
+                scalars,
                 scheme,
+                root_meta_fields: ROOT_META_FIELDS
+                    .iter()
+                    .map(|&source| meta_field(source))
+                    .collect(),
                 types,
+                typename_field: meta_field(TYPENAME_FIELD),
                 unions,
             })
         } else {
@@ -356,3 +1593,70 @@ impl Processed {
         }
     }
 }
+
+// redefinition of item in data map, builtin scalars have no span to point to
+fn already_defined(message: String, previous: &unprocessed::GsdlDataItem, span: Span) -> Diagnostic {
+    match previous.span() {
+        Some(previous_span) => Diagnostic::duplicate(message, previous_span, span),
+        None => Diagnostic::error(Code::DuplicateDefinition, message).with_span(span),
+    }
+}
+
+fn field_deprecations<'a>(
+    owner: &str,
+    fields: data::processed::Fields<'a>,
+    result: &mut Vec<data::processed::Deprecation<'a>>,
+) {
+    use graphql::data::processed::{Deprecation, SchemaElement};
+
+    for field in fields {
+        for argument in field.arguments() {
+            if let Some(reason) = argument.directives().deprecation() {
+                result.push(Deprecation {
+                    coordinate: format!("{}.{}({}:)", owner, field.name, argument.name),
+                    reason,
+                    element: SchemaElement::FieldArgument(argument),
+                });
+            }
+        }
+        if let Some(reason) = field.directives().deprecation() {
+            result.push(Deprecation {
+                coordinate: format!("{}.{}", owner, field.name),
+                reason,
+                element: SchemaElement::Field(field),
+            });
+        }
+    }
+}
+
+fn reserved_name(owner: &str, name: &str, span: Span) -> Option<Diagnostic> {
+    if name.starts_with("__") {
+        Some(
+            Diagnostic::error(
+                Code::ReservedName,
+                format!(
+                    "{} has reserved name, names starting with __ are used by introspection",
+                    owner
+                ),
+            ).with_span(span),
+        )
+    } else {
+        None
+    }
+}
+
+fn reserved_field_names(owner: &str, fields: &[unprocessed::Field]) -> Vec<Diagnostic> {
+    let mut errors = vec![];
+    for field in fields {
+        let field_owner = format!("{} field {}", owner, field.name);
+        errors.extend(reserved_name(&field_owner, &field.name, field.span));
+        for argument in &field.arguments {
+            errors.extend(reserved_name(
+                &format!("{} argument {}", field_owner, argument.name),
+                &argument.name,
+                argument.span,
+            ));
+        }
+    }
+    errors
+}
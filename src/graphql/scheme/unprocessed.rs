@@ -1,88 +1,145 @@
-use graphql::data::unprocessed::{Enum, Field, GsdlItem, Interface, SchemeEntryPoints, Type, Union};
+use graphql::data::unprocessed::{CustomScalar, Directive, DirectiveDefinition, Enum, Extension, Field,
+                                 GsdlItem, Input, Interface, NameIndex, Named, Scalar,
+                                 SchemeEntryPoints, Type, Union};
+use graphql::diagnostic::{Code, Diagnostic, Span};
 use graphql::scheme::Processed;
+use std::mem;
 
 pub struct Unprocessed {
+    pub directive_definitions: Vec<DirectiveDefinition>,
     pub enums: Vec<Enum>,
+    pub inputs: Vec<Input>,
     pub interfaces: Vec<Interface>,
 
     // Scheme entry points
     pub query: Option<String>,
     pub mutate: Option<String>,
+    pub subscription: Option<String>,
+    pub scheme_description: Option<String>,
+    pub scheme_directives: Vec<Directive>,
     pub scheme_entry_points_encountered: bool,
+    pub scheme_span: Option<Span>,
 
+    pub scalars: Vec<CustomScalar>,
     pub types: Vec<Type>,
     pub unions: Vec<Union>,
+
+    // merged into definitions above when processed
+    pub extensions: Vec<Extension>,
 }
 
 impl Unprocessed {
-    fn from(items: Vec<GsdlItem>) -> Result<Unprocessed, Vec<String>> {
+    fn from(items: Vec<GsdlItem>) -> Result<Unprocessed, Vec<Diagnostic>> {
         let mut result = Unprocessed {
+            directive_definitions: vec![],
             enums: vec![],
+            inputs: vec![],
             interfaces: vec![],
             query: None,
             mutate: None,
+            subscription: None,
+            scheme_description: None,
+            scheme_directives: vec![],
             scheme_entry_points_encountered: false,
+            scheme_span: None,
+            scalars: vec![],
             types: vec![],
             unions: vec![],
+            extensions: vec![],
         };
 
-        items.into_iter().map(|item| result.add_item(item)).fold(
-            Ok(()),
-            |a: Result<(), Vec<String>>, b| {
-                b.map_err(|c| match a {
-                    Ok(()) => c,
-                    Err(errors) => {
-                        let mut new_errors = errors.to_owned();
-                        new_errors.extend(c);
-                        new_errors
-                    }
-                })
-            },
-        )?;
+        let mut errors = vec![];
+        for item in items {
+            if let Err(mut item_errors) = result.add_item(item) {
+                errors.append(&mut item_errors);
+            }
+        }
 
-        Ok(result)
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
     }
 
-    pub fn process(self) -> Result<Processed, Vec<String>> {
+    pub fn process(mut self) -> Result<Processed, Vec<Diagnostic>> {
+        self.apply_extensions()?;
         Processed::from(self)
     }
 
-    fn add_item(&mut self, item: GsdlItem) -> Result<(), Vec<String>> {
+    fn add_item(&mut self, item: GsdlItem) -> Result<(), Vec<Diagnostic>> {
         match item {
+            GsdlItem::DirectiveDefinition(directive_definition) => {
+                self.add_directive_definition(directive_definition)
+            }
             GsdlItem::Enum(gsdl_enum) => self.add_enum(gsdl_enum),
+            GsdlItem::Extension(extension) => self.add_extension(extension),
+            GsdlItem::Input(input) => self.add_input(input),
             GsdlItem::Interface(interface) => self.add_interface(interface),
             GsdlItem::SchemeEntryPoints(scheme_entry_points) => {
                 self.add_scheme_entry_points(scheme_entry_points)
             }
+            GsdlItem::Scalar(scalar) => self.add_scalar(scalar),
             GsdlItem::Type(gsdl_type) => self.add_type(gsdl_type),
             GsdlItem::Union(union) => self.add_union(union),
         }
     }
 
-    fn add_enum(&mut self, gsdl_enum: Enum) -> Result<(), Vec<String>> {
-        let mut sorted_values = gsdl_enum.values;
-        sorted_values.sort_unstable();
-
+    fn add_directive_definition(
+        &mut self,
+        directive_definition: DirectiveDefinition,
+    ) -> Result<(), Vec<Diagnostic>> {
         let mut errors = vec![];
-        // check value uniqueness
+        // check argument name uniqueness
+        for (argument, next_argument) in directive_definition
+            .argument_index
+            .duplicates(&directive_definition.arguments)
         {
-            let mut iter = sorted_values.iter().peekable();
-            while let Some(value) = iter.next() {
-                if let Some(&next_value) = iter.peek() {
-                    if *next_value == *value {
-                        errors.push(format!(
-                            "Enum {} has duplicate value {}",
-                            gsdl_enum.name, value
-                        ))
-                    }
-                }
+            errors.push(Diagnostic::duplicate(
+                format!(
+                    "Directive @{} has duplicate argument named {}",
+                    directive_definition.name, argument.name
+                ),
+                argument.span,
+                next_argument.span,
+            ))
+        }
+        // check location uniqueness
+        for (i, location) in directive_definition.locations.iter().enumerate() {
+            if directive_definition.locations[..i].contains(location) {
+                errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!(
+                            "Directive @{} has duplicate location {:?}",
+                            directive_definition.name, location
+                        ),
+                    ).with_span(directive_definition.span),
+                )
             }
         }
 
-        self.enums.push(Enum {
-            name: gsdl_enum.name,
-            values: sorted_values,
-        });
+        self.directive_definitions.push(directive_definition);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn add_enum(&mut self, gsdl_enum: Enum) -> Result<(), Vec<Diagnostic>> {
+        let mut errors = vec![];
+        // check value uniqueness
+        for (value, next_value) in gsdl_enum.value_index.duplicates(&gsdl_enum.values) {
+            errors.push(Diagnostic::duplicate(
+                format!("Enum {} has duplicate value {}", gsdl_enum.name, value.name),
+                value.span,
+                next_value.span,
+            ))
+        }
+
+        self.enums.push(gsdl_enum);
 
         if errors.is_empty() {
             Ok(())
@@ -91,38 +148,57 @@ impl Unprocessed {
         }
     }
 
-    fn add_interface(&mut self, interface: Interface) -> Result<(), Vec<String>> {
-        let mut sorted_fields = interface.fields;
-        // Note using sort_unstable_by instead of sort_unstable_by_key due to latter being more restrictive than former
-        // See https://github.com/rust-lang/rust/issues/34162 for details
-        sorted_fields.sort_unstable_by(|l, r| l.name.cmp(&r.name));
+    // extensions are applied once all definitions are known, as base may come later in source
+    fn add_extension(&mut self, extension: Extension) -> Result<(), Vec<Diagnostic>> {
+        self.extensions.push(extension);
+        Ok(())
+    }
 
+    fn add_input(&mut self, input: Input) -> Result<(), Vec<Diagnostic>> {
         let mut errors = vec![];
         // check field name uniqueness
-        {
-            let mut iter = sorted_fields.iter().peekable();
-            while let Some(field) = iter.next() {
-                if let Some(&next_field) = iter.peek() {
-                    if *next_field.name == *field.name {
-                        errors.push(format!(
-                            "Interface {} has duplicate field named {}",
-                            interface.name, field.name
-                        ))
-                    }
-                }
-            }
+        for (field, next_field) in input.field_index.duplicates(&input.fields) {
+            errors.push(Diagnostic::duplicate(
+                format!(
+                    "Input {} has duplicate field named {}",
+                    input.name, field.name
+                ),
+                field.span,
+                next_field.span,
+            ))
         }
-        // check argument name uniqueness per field
+
+        self.inputs.push(input);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn add_interface(&mut self, interface: Interface) -> Result<(), Vec<Diagnostic>> {
+        let mut errors = vec![];
         let prefix = format!("Interface {}", interface.name);
-        let processed_fields: Vec<Field> = sorted_fields
-            .into_iter()
-            .map(|f| Unprocessed::process_field(f, &mut errors, &prefix))
-            .collect();
+        // check interface name uniqueness
+        Unprocessed::check_implements(&interface.implements, interface.span, &mut errors, &prefix);
+        // check field name uniqueness
+        for (field, next_field) in interface.field_index.duplicates(&interface.fields) {
+            errors.push(Diagnostic::duplicate(
+                format!(
+                    "Interface {} has duplicate field named {}",
+                    interface.name, field.name
+                ),
+                field.span,
+                next_field.span,
+            ))
+        }
+        // check argument name uniqueness per field
+        for field in &interface.fields {
+            Unprocessed::check_field(field, &mut errors, &prefix);
+        }
 
-        self.interfaces.push(Interface {
-            name: interface.name,
-            fields: processed_fields,
-        });
+        self.interfaces.push(interface);
 
         if errors.is_empty() {
             Ok(())
@@ -134,46 +210,63 @@ impl Unprocessed {
     fn add_scheme_entry_points(
         &mut self,
         scheme_entry_points: SchemeEntryPoints,
-    ) -> Result<(), Vec<String>> {
+    ) -> Result<(), Vec<Diagnostic>> {
         let mut errors = vec![];
 
         if self.scheme_entry_points_encountered {
-            errors.push(format!(
-                "Duplicate schema entry points : already set query: {:?}, mutate {:?}, new  {:?}",
-                self.query, self.mutate, scheme_entry_points
-            ))
+            let message = format!(
+                "Duplicate schema entry points : already set query: {:?}, mutate {:?}, \
+                 subscription {:?}, new  {:?}",
+                self.query, self.mutate, self.subscription, scheme_entry_points
+            );
+            errors.push(match self.scheme_span {
+                Some(span) => Diagnostic::duplicate(message, span, scheme_entry_points.span),
+                None => Diagnostic::error(Code::DuplicateDefinition, message),
+            })
         }
         self.scheme_entry_points_encountered = true;
+        self.scheme_span = Some(scheme_entry_points.span);
+        self.scheme_description = scheme_entry_points.description;
+        self.scheme_directives.extend(scheme_entry_points.directives);
 
         if scheme_entry_points.entries.is_empty() {
-            errors.push(String::from("Empty schema entry points  encountered"))
+            errors.push(
+                Diagnostic::error(
+                    Code::InvalidSchema,
+                    String::from("Empty schema entry points  encountered"),
+                ).with_span(scheme_entry_points.span),
+            )
         }
 
         for (name, value) in scheme_entry_points.entries {
-            match name.as_ref() {
-                "query" => {
-                    match self.query {
-                        None => (),
-                        Some(ref old_value) => errors.push(format!(
-                            "Duplicate query entry in scheme: old {}, new {}",
-                            old_value, value
-                        )),
-                    };
-                    self.query = Some(value)
+            // "mutate" is kept for compatibility, the spec uses "mutation"
+            let entry_point = match name.as_ref() {
+                "query" => &mut self.query,
+                "mutation" | "mutate" => &mut self.mutate,
+                "subscription" => &mut self.subscription,
+                _ => {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::InvalidSchema,
+                            format!("Unknown entry {} in scheme", name),
+                        ).with_span(scheme_entry_points.span),
+                    );
+                    continue;
                 }
-                "mutate" => {
-                    match self.mutate {
-                        None => (),
-                        Some(ref old_value) => errors.push(format!(
-                            "Duplicate mutate entry in scheme: old {}, new {}",
-                            old_value, value
-                        )),
-                    };
-                    ;
-                    self.mutate = Some(value)
-                }
-                _ => errors.push(format!("Unknown entry {} in scheme", name)),
-            }
+            };
+            match *entry_point {
+                None => (),
+                Some(ref old_value) => errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!(
+                            "Duplicate {} entry in scheme: old {}, new {}",
+                            name, old_value, value
+                        ),
+                    ).with_span(scheme_entry_points.span),
+                ),
+            };
+            *entry_point = Some(value)
         }
 
         if errors.is_empty() {
@@ -183,54 +276,56 @@ impl Unprocessed {
         }
     }
 
-    fn add_type(&mut self, gsdl_type: Type) -> Result<(), Vec<String>> {
-        let mut sorted_implements = gsdl_type.implements;
-        sorted_implements.sort_unstable();
+    fn add_scalar(&mut self, scalar: CustomScalar) -> Result<(), Vec<Diagnostic>> {
+        self.scalars.push(scalar);
+        Ok(())
+    }
+
+    fn add_type(&mut self, gsdl_type: Type) -> Result<(), Vec<Diagnostic>> {
+        let mut errors = vec![];
+        let prefix = format!("Type {}", gsdl_type.name);
+        // check interface name uniqueness
+        Unprocessed::check_implements(&gsdl_type.implements, gsdl_type.span, &mut errors, &prefix);
+        // check field name uniqueness
+        for (field, next_field) in gsdl_type.field_index.duplicates(&gsdl_type.fields) {
+            errors.push(Diagnostic::duplicate(
+                format!(
+                    "Type {} has duplicate field named {}",
+                    gsdl_type.name, field.name
+                ),
+                field.span,
+                next_field.span,
+            ))
+        }
+        // check argument name uniqueness per field
+        for field in &gsdl_type.fields {
+            Unprocessed::check_field(field, &mut errors, &prefix);
+        }
+
+        self.types.push(gsdl_type);
 
-        let mut sorted_fields = gsdl_type.fields;
-        // Note using sort_unstable_by instead of sort_unstable_by_key due to latter being more restrictive than former
-        // See https://github.com/rust-lang/rust/issues/34162 for details
-        sorted_fields.sort_unstable_by(|l, r| l.name.cmp(&r.name));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 
+    fn add_union(&mut self, union: Union) -> Result<(), Vec<Diagnostic>> {
         let mut errors = vec![];
-        {
-            // check interface name uniqueness
-            let mut iter = sorted_implements.iter().peekable();
-            while let Some(interface) = iter.next() {
-                if let Some(&next_interface) = iter.peek() {
-                    if *next_interface == *interface {
-                        errors.push(format!(
-                            "Type {} implements named {} twice",
-                            gsdl_type.name, interface
-                        ))
-                    }
-                }
-            }
-            //check filed name uniquness per field
-            let mut iter = sorted_fields.iter().peekable();
-            while let Some(field) = iter.next() {
-                if let Some(&next_field) = iter.peek() {
-                    if *next_field.name == *field.name {
-                        errors.push(format!(
-                            "Type {} has duplicate field named {}",
-                            gsdl_type.name, field.name
-                        ))
-                    }
-                }
+        // check member uniqueness
+        for (i, member) in union.members.iter().enumerate() {
+            if union.members[..i].contains(member) {
+                errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!("Union {} has duplicate member {}", union.name, member),
+                    ).with_span(union.span),
+                )
             }
         }
 
-        let prefix = format!("Type {}", gsdl_type.name);
-        let processed_fields: Vec<Field> = sorted_fields
-            .into_iter()
-            .map(|f| Unprocessed::process_field(f, &mut errors, &prefix))
-            .collect();
-
-        self.types.push(Type {
-            name: gsdl_type.name,
-            implements: sorted_implements,
-            fields: processed_fields,
-        });
+        self.unions.push(union);
 
         if errors.is_empty() {
             Ok(())
@@ -239,31 +334,133 @@ impl Unprocessed {
         }
     }
 
-    fn add_union(&mut self, union: Union) -> Result<(), Vec<String>> {
-        let mut sorted_members = union.members;
-        sorted_members.sort_unstable();
-
+    fn apply_extensions(&mut self) -> Result<(), Vec<Diagnostic>> {
         let mut errors = vec![];
-        // check member uniqueness
-        {
-            let mut iter = sorted_members.iter().peekable();
-            while let Some(member) = iter.next() {
-                if let Some(&next_member) = iter.peek() {
-                    if *next_member == *member {
-                        errors.push(format!(
-                            "Union {} has duplicate member {}",
-                            union.name, member
-                        ))
+        for extension in mem::take(&mut self.extensions) {
+            let owner = extension.owner();
+            let kind = extension.kind();
+            let span = extension.span();
+            match extension {
+                Extension::Enum(extension) => {
+                    match self.enums.iter_mut().find(|e| e.name == extension.name) {
+                        Some(gsdl_enum) => {
+                            gsdl_enum.directives.extend(extension.directives);
+                            Unprocessed::extend_items(
+                                &mut gsdl_enum.values,
+                                extension.values,
+                                |v| v.span,
+                                &mut errors,
+                                &format!("Extension of {} adds value", owner),
+                            );
+                            gsdl_enum.value_index = NameIndex::new(&gsdl_enum.values);
+                        }
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
+                    }
+                }
+                Extension::Input(extension) => {
+                    match self.inputs.iter_mut().find(|i| i.name == extension.name) {
+                        Some(input) => {
+                            input.directives.extend(extension.directives);
+                            Unprocessed::extend_items(
+                                &mut input.fields,
+                                extension.fields,
+                                |f| f.span,
+                                &mut errors,
+                                &format!("Extension of {} adds field", owner),
+                            );
+                            input.field_index = NameIndex::new(&input.fields);
+                        }
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
+                    }
+                }
+                Extension::Interface(extension) => {
+                    match self.interfaces.iter_mut().find(|i| i.name == extension.name) {
+                        Some(interface) => {
+                            Unprocessed::extend_implements(
+                                &mut interface.implements,
+                                extension.implements,
+                                span,
+                                &mut errors,
+                                &owner,
+                            );
+                            interface.directives.extend(extension.directives);
+                            let prefix = format!("Interface {}", interface.name);
+                            for field in &extension.fields {
+                                Unprocessed::check_field(field, &mut errors, &prefix);
+                            }
+                            Unprocessed::extend_items(
+                                &mut interface.fields,
+                                extension.fields,
+                                |f| f.span,
+                                &mut errors,
+                                &format!("Extension of {} adds field", owner),
+                            );
+                            interface.field_index = NameIndex::new(&interface.fields);
+                        }
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
+                    }
+                }
+                Extension::Scalar(extension) => {
+                    match self.scalars.iter_mut().find(|s| s.name == extension.name) {
+                        Some(scalar) => scalar.directives.extend(extension.directives),
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
+                    }
+                }
+                Extension::Scheme(extension) => self.extend_scheme(extension, &mut errors),
+                Extension::Type(extension) => {
+                    match self.types.iter_mut().find(|t| t.name == extension.name) {
+                        Some(gsdl_type) => {
+                            Unprocessed::extend_implements(
+                                &mut gsdl_type.implements,
+                                extension.implements,
+                                span,
+                                &mut errors,
+                                &owner,
+                            );
+                            gsdl_type.directives.extend(extension.directives);
+                            let prefix = format!("Type {}", gsdl_type.name);
+                            for field in &extension.fields {
+                                Unprocessed::check_field(field, &mut errors, &prefix);
+                            }
+                            Unprocessed::extend_items(
+                                &mut gsdl_type.fields,
+                                extension.fields,
+                                |f| f.span,
+                                &mut errors,
+                                &format!("Extension of {} adds field", owner),
+                            );
+                            gsdl_type.field_index = NameIndex::new(&gsdl_type.fields);
+                        }
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
+                    }
+                }
+                Extension::Union(extension) => {
+                    match self.unions.iter_mut().find(|u| u.name == extension.name) {
+                        Some(union) => {
+                            union.directives.extend(extension.directives);
+                            for member in extension.members {
+                                if union.members.contains(&member) {
+                                    errors.push(
+                                        Diagnostic::error(
+                                            Code::DuplicateDefinition,
+                                            format!(
+                                                "Extension of {} adds member {}, \
+                                                 which is already included",
+                                                owner, member
+                                            ),
+                                        ).with_span(span),
+                                    )
+                                } else {
+                                    union.members.push(member);
+                                }
+                            }
+                        }
+                        None => errors.push(self.missing_base(kind, &extension.name, span)),
                     }
                 }
             }
         }
 
-        self.unions.push(Union {
-            name: union.name,
-            members: sorted_members,
-        });
-
         if errors.is_empty() {
             Ok(())
         } else {
@@ -271,39 +468,160 @@ impl Unprocessed {
         }
     }
 
-    fn process_field(field: Field, errors: &mut Vec<String>, prefix: &str) -> Field {
-        let mut result = field;
+    fn extend_scheme(&mut self, extension: SchemeEntryPoints, errors: &mut Vec<Diagnostic>) {
+        if !self.scheme_entry_points_encountered {
+            errors.push(
+                Diagnostic::error(
+                    Code::UndefinedReference,
+                    String::from("Cannot extend schema, which is not defined"),
+                ).with_span(extension.span),
+            );
+            return;
+        }
+        self.scheme_directives.extend(extension.directives);
+        for (name, value) in extension.entries {
+            let entry_point = match name.as_ref() {
+                "query" => &mut self.query,
+                "mutation" | "mutate" => &mut self.mutate,
+                "subscription" => &mut self.subscription,
+                _ => {
+                    errors.push(
+                        Diagnostic::error(
+                            Code::InvalidSchema,
+                            format!("Unknown entry {} in scheme", name),
+                        ).with_span(extension.span),
+                    );
+                    continue;
+                }
+            };
+            match *entry_point {
+                Some(ref old_value) => errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!(
+                            "Extension of schema sets {}, which is already set to {}",
+                            name, old_value
+                        ),
+                    ).with_span(extension.span),
+                ),
+                None => *entry_point = Some(value),
+            }
+        }
+    }
 
-        // Note using sort_unstable_by instead of sort_unstable_by_key due to latter being more restrictive than former
-        // See https://github.com/rust-lang/rust/issues/34162 for details
-        result
-            .arguments
-            .sort_unstable_by(|l, r| l.name.cmp(&r.name));
+    // appends extension items, those with names already in use are reported instead
+    fn extend_items<T: Named>(
+        items: &mut Vec<T>,
+        extension: Vec<T>,
+        span: fn(&T) -> Span,
+        errors: &mut Vec<Diagnostic>,
+        message: &str,
+    ) {
+        for item in extension {
+            match items.iter().find(|i| i.name() == item.name()) {
+                Some(existing) => errors.push(Diagnostic::duplicate(
+                    format!("{} {}, which is already defined", message, item.name()),
+                    span(existing),
+                    span(&item),
+                )),
+                None => items.push(item),
+            }
+        }
+    }
 
-        {
-            let mut iter = result.arguments.iter().peekable();
-            while let Some(argument) = iter.next() {
-                if let Some(&next_argument) = iter.peek() {
-                    if *next_argument.name == *argument.name {
-                        errors.push(format!(
-                            "{} field {} has duplicate argument named {}",
-                            prefix, result.name, argument.name
-                        ))
-                    }
-                }
+    fn extend_implements(
+        implements: &mut Vec<String>,
+        extension: Vec<String>,
+        span: Span,
+        errors: &mut Vec<Diagnostic>,
+        owner: &str,
+    ) {
+        for interface in extension {
+            if implements.contains(&interface) {
+                errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!(
+                            "Extension of {} implements {}, which is already implemented",
+                            owner, interface
+                        ),
+                    ).with_span(span),
+                )
+            } else {
+                implements.push(interface);
+            }
+        }
+    }
+
+    // extended item is not defined or is of other kind
+    fn missing_base(&self, kind: &str, name: &str, span: Span) -> Diagnostic {
+        let found = if self.enums.iter().any(|e| e.name == name) {
+            Some("enum")
+        } else if self.inputs.iter().any(|i| i.name == name) {
+            Some("input")
+        } else if self.interfaces.iter().any(|i| i.name == name) {
+            Some("interface")
+        } else if self.scalars.iter().any(|s| s.name == name) {
+            Some("scalar")
+        } else if Scalar::iter().any(|&(_, scalar_name)| scalar_name == name) {
+            Some("builtin scalar")
+        } else if self.types.iter().any(|t| t.name == name) {
+            Some("type")
+        } else if self.unions.iter().any(|u| u.name == name) {
+            Some("union")
+        } else {
+            None
+        };
+        match found {
+            Some(found) => Diagnostic::error(
+                Code::WrongKind,
+                format!("Cannot extend {} {}, {} is {}", kind, name, name, found),
+            ),
+            None => Diagnostic::error(
+                Code::UndefinedReference,
+                format!("Cannot extend {} {}, which is not defined", kind, name),
+            ),
+        }.with_span(span)
+    }
+
+    fn check_implements(
+        implements: &[String],
+        span: Span,
+        errors: &mut Vec<Diagnostic>,
+        prefix: &str,
+    ) {
+        for (i, interface) in implements.iter().enumerate() {
+            if implements[..i].contains(interface) {
+                errors.push(
+                    Diagnostic::error(
+                        Code::DuplicateDefinition,
+                        format!("{} implements named {} twice", prefix, interface),
+                    ).with_span(span),
+                )
             }
         }
+    }
 
-        result
+    fn check_field(field: &Field, errors: &mut Vec<Diagnostic>, prefix: &str) {
+        for (argument, next_argument) in field.argument_index.duplicates(&field.arguments) {
+            errors.push(Diagnostic::duplicate(
+                format!(
+                    "{} field {} has duplicate argument named {}",
+                    prefix, field.name, argument.name
+                ),
+                argument.span,
+                next_argument.span,
+            ))
+        }
     }
 }
 
 pub trait UnprocessedSource {
-    fn build_gsdl(self) -> Result<Unprocessed, Vec<String>>;
+    fn build_gsdl(self) -> Result<Unprocessed, Vec<Diagnostic>>;
 }
 
 impl UnprocessedSource for Vec<GsdlItem> {
-    fn build_gsdl(self) -> Result<Unprocessed, Vec<String>> {
+    fn build_gsdl(self) -> Result<Unprocessed, Vec<Diagnostic>> {
         Unprocessed::from(self)
     }
 }
@@ -1,17 +1,89 @@
-use graphql::data::unprocessed::{Argument, InnerTypeKind, Value};
-use graphql::generated_lalrpop::{parse_Directive, parse_Enum, parse_Field, parse_Interface,
-                                 parse_Name, parse_OuterType, parse_Type, parse_Union};
+use graphql::data::owned;
+use graphql::data::processed;
+use graphql::data::processed::{GsdlDataItem, LookupError, SchemaElement};
+use graphql::data::unprocessed;
+use graphql::data::unprocessed::{Argument, CustomScalar, Directive, DirectiveDefinition, DirectiveLocation, Enum,
+                                 Extension, Field, Input, Interface, OuterType, Type, Union, Value};
+use graphql::generated_lalrpop;
+use graphql::lexer::{Lexer, Tok};
+use graphql::scheme::GsdlDataMap;
+use graphql::{parse_gsdl, parse_gsdl_lenient, parse_introspection_json, Code, Diagnostic};
+use lalrpop_util::ParseError;
+use std::thread;
+
+// generated parsers take tokens, tests pass source text
+macro_rules! source_parsers {
+    ($($parser:ident -> $result:ty),*) => {
+        $(
+            #[allow(non_snake_case)]
+            fn $parser(source: &str) -> Result<$result, ParseError<usize, Tok<'_>, Diagnostic>> {
+                generated_lalrpop::$parser(Lexer::new(source))
+            }
+        )*
+    };
+}
+
+source_parsers!(
+    parse_Directive -> Directive,
+    parse_DirectiveDefinition -> DirectiveDefinition,
+    parse_Enum -> Enum,
+    parse_Extension -> Extension,
+    parse_Field -> Field,
+    parse_Input -> Input,
+    parse_Interface -> Interface,
+    parse_Name -> &str,
+    parse_OuterType -> OuterType,
+    parse_Scalar -> CustomScalar,
+    parse_Type -> Type,
+    parse_Union -> Union,
+    parse_Value -> Value
+);
+
+fn messages(errors: Vec<Diagnostic>) -> Vec<String> {
+    errors.into_iter().map(|e| e.message).collect()
+}
 
 #[test]
 fn parse_name_start_with_letter() {
     assert_eq!(parse_Name("AB12").unwrap(), "AB12");
 }
 
+#[test]
+fn parse_name_keyword() {
+    assert_eq!(parse_Name("input").unwrap(), "input");
+    assert_eq!(parse_Name("type").unwrap(), "type");
+}
+
 #[test]
 fn parse_name_starts_digit_fails() {
     assert!(parse_Name("5AB12!").is_err());
 }
 
+#[test]
+fn parse_name_with_underscores() {
+    assert_eq!(parse_Name("_id").unwrap(), "_id");
+    assert_eq!(parse_Name("created_at").unwrap(), "created_at");
+    assert_eq!(parse_Name("SNAKE_CASE_2").unwrap(), "SNAKE_CASE_2");
+    assert_eq!(parse_Name("null").unwrap(), "null");
+}
+
+#[test]
+fn parse_non_ascii_name_fails_unless_lenient() {
+    let source = "scheme { query: Query } type Query { café: Int }";
+    let errors = parse_gsdl(String::from(source)).err().unwrap();
+    assert_eq!(errors[0].code, Code::Syntax);
+    assert_eq!(
+        errors[0].message,
+        "Invalid name café, names can contain only ASCII letters, digits and underscores"
+    );
+
+    let processed = parse_gsdl_lenient(String::from(source)).unwrap();
+    assert!(processed.query().field("café").is_some());
+
+    let tokens: Vec<Tok> = Lexer::lenient("Größe_1").map(|t| t.unwrap().1).collect();
+    assert_eq!(tokens, vec![Tok::Name("Größe_1")]);
+}
+
 #[test]
 fn parse_name_starts_non_letter_fails() {
     assert!(parse_Name("@AB12").is_err());
@@ -26,18 +98,14 @@ fn parse_name_non_letter_fails() {
 fn parse_non_vector_non_nullable() {
     let t = parse_OuterType("Type!").unwrap();
 
-    assert_eq!(t.nullable, false);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t, OuterType::named("Type", false));
 }
 
 #[test]
 fn parse_non_vector_nullable() {
     let t = parse_OuterType("Type").unwrap();
 
-    assert_eq!(t.nullable, true);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t, OuterType::named("Type", true));
 }
 
 #[test]
@@ -51,36 +119,28 @@ fn parse_non_vector_with_garbage_fails() {
 fn parse_vector_not_nullable_inner_not_nullable() {
     let t = parse_OuterType("[Type!]!").unwrap();
 
-    assert_eq!(t.nullable, false);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::Vector { nullable: false });
+    assert_eq!(t, OuterType::list(OuterType::named("Type", false), false));
 }
 
 #[test]
 fn parse_vector_not_nullable_inner_nullable() {
     let t = parse_OuterType("[Type]!").unwrap();
 
-    assert_eq!(t.nullable, false);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::Vector { nullable: true });
+    assert_eq!(t, OuterType::list(OuterType::named("Type", true), false));
 }
 
 #[test]
 fn parse_vector_nullable_inner_not_nullable() {
     let t = parse_OuterType("[Type!]").unwrap();
 
-    assert_eq!(t.nullable, true);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::Vector { nullable: false });
+    assert_eq!(t, OuterType::list(OuterType::named("Type", false), true));
 }
 
 #[test]
 fn parse_vector_nullable_inner_nullable() {
     let t = parse_OuterType("[Type]").unwrap();
 
-    assert_eq!(t.nullable, true);
-    assert_eq!(t.inner.name, "Type");
-    assert_eq!(t.inner.kind, InnerTypeKind::Vector { nullable: true });
+    assert_eq!(t, OuterType::list(OuterType::named("Type", true), true));
 }
 
 #[test]
@@ -97,6 +157,116 @@ fn parse_vector_with_inner_garbage_fails() {
     assert!(parse_OuterType("[Type?!]").is_err());
 }
 
+#[test]
+fn parse_nested_vectors() {
+    let t = parse_OuterType("[[Float!]!]!").unwrap();
+
+    assert_eq!(
+        t,
+        OuterType::list(OuterType::list(OuterType::named("Float", false), false), false)
+    );
+    assert_eq!(t.name(), "Float");
+
+    let t = parse_OuterType("[[[Type]]!]").unwrap();
+
+    assert_eq!(
+        t,
+        OuterType::list(
+            OuterType::list(OuterType::list(OuterType::named("Type", true), true), false),
+            true
+        )
+    );
+}
+
+#[test]
+fn parse_nested_vectors_with_garbage_fails() {
+    assert!(parse_OuterType("[[Type]").is_err());
+    assert!(parse_OuterType("[[Type]]]").is_err());
+    assert!(parse_OuterType("[[Type]!!]").is_err());
+    assert!(parse_OuterType("[]").is_err());
+}
+
+#[test]
+fn process_nested_vectors() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { matrix: [[Float!]!]! }",
+    )).unwrap();
+
+    let query = processed.query();
+    let field = query.fields().into_iter().next().unwrap();
+    assert_eq!(*field.name, "matrix");
+    let field_type = field.field_type().unwrap();
+    assert!(!field_type.nullable);
+    assert_eq!(field_type.named_type().name(), "Float");
+}
+
+#[test]
+fn process_field_types_and_default_values() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Order { ASC DESC }
+        input Page { size: Int = 10 order: Order = ASC }
+        type Query { items(page: Page = { size: 20 } order: [Order!] = DESC limit: Int): Query }",
+    )).unwrap();
+
+    let query = processed.query();
+    let field = query.fields().into_iter().next().unwrap();
+    let field_type = field.field_type().unwrap();
+    assert!(field_type.nullable);
+    match *field_type.named_type() {
+        GsdlDataItem::Type(ref gsdl_type) => assert_eq!(gsdl_type.name, "Query"),
+        ref other => panic!("unexpected field type {:?}", other),
+    }
+
+    let arguments: Vec<_> = field.arguments().into_iter().collect();
+    assert_eq!(
+        arguments[0].default_value(),
+        Some(&Value::Object(vec![(String::from("size"), Value::Int(20))]))
+    );
+    assert_eq!(
+        arguments[1].default_value(),
+        Some(&Value::Enum(String::from("DESC")))
+    );
+    assert_eq!(arguments[2].default_value(), None);
+}
+
+#[test]
+fn process_invalid_default_values_fail() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Order { ASC DESC }
+        input Page { size: Int = \"ten\" order: Order! = null }
+        type Query { items(page: Page = { limit: 1 } order: Order = UP): Int }",
+    )).err()
+        .unwrap();
+
+    assert!(errors.iter().all(|e| e.code == Code::InvalidValue));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type Query field items argument page has invalid default value: Page has no field limit",
+            "Type Query field items argument order has invalid default value: expected Order, found UP",
+            "Input Page field size has invalid default value: expected Int, found \"ten\"",
+            "Input Page field order has invalid default value: expected Order!, found null",
+        ]
+    );
+}
+
+#[test]
+fn process_nested_vectors_undefined_type_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { matrix: [[Cell!]!]! (filter: [[Filter]]) }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("return type Cell"));
+    assert!(errors[1].contains("references type Filter"));
+}
+
 #[test]
 fn parse_directive_zero_parameters() {
     assert!(parse_Directive("@directive").is_ok());
@@ -117,15 +287,138 @@ fn parse_directive_3_parameters() {
     assert!(parse_Directive("@directive(a:b c:d e:f)").is_ok());
 }
 
+#[test]
+fn parse_directive_arguments_kept() {
+    let t = parse_Directive("@directive(a: 1 b: B)").unwrap();
+
+    assert_eq!(t.name, "directive");
+    assert_eq!(t.argument("a"), Option::Some(&Value::Int(1)));
+    assert_eq!(t.argument("b"), Option::Some(&Value::Enum(String::from("B"))));
+    assert_eq!(t.argument("c"), Option::None);
+}
+
+#[test]
+fn parse_directive_value_parameters() {
+    assert!(parse_Directive("@directive(a: 1 b: \"c\" d: [true null] e: {f: 1.5})").is_ok());
+}
+
+#[test]
+fn parse_value_scalars() {
+    assert_eq!(parse_Value("10").unwrap(), Value::Int(10));
+    assert_eq!(parse_Value("-0").unwrap(), Value::Int(0));
+    assert_eq!(parse_Value("1.5").unwrap(), Value::Float(1.5));
+    assert_eq!(parse_Value("-2e3").unwrap(), Value::Float(-2000.0));
+    assert_eq!(parse_Value("1.5E-1").unwrap(), Value::Float(0.15));
+    assert_eq!(parse_Value("true").unwrap(), Value::Boolean(true));
+    assert_eq!(parse_Value("false").unwrap(), Value::Boolean(false));
+    assert_eq!(parse_Value("null").unwrap(), Value::Null);
+    assert_eq!(
+        parse_Value("RED").unwrap(),
+        Value::Enum(String::from("RED"))
+    );
+}
+
+#[test]
+fn parse_value_string_escapes() {
+    assert_eq!(
+        parse_Value(r#""a\"b\\c\/d\n\u00e9""#).unwrap(),
+        Value::String(String::from("a\"b\\c/d\n\u{e9}"))
+    );
+    assert!(parse_Value(r#""\q""#).is_err());
+    assert!(parse_Value(r#""\u12""#).is_err());
+}
+
+#[test]
+fn parse_value_string_signed_unicode_escape_fails() {
+    assert!(parse_Value(r#""\u+123""#).is_err());
+    assert!(parse_Value(r#""\u-123""#).is_err());
+}
+
+#[test]
+fn parse_value_string_surrogate_pair() {
+    assert_eq!(
+        parse_Value(r#""a\uD83D\uDE00b""#).unwrap(),
+        Value::String(String::from("a\u{1F600}b"))
+    );
+    assert_eq!(
+        parse_gsdl(String::from(r#"type Query { a(x: String = "\uD83D\uDE00"): Int }"#))
+            .unwrap()
+            .query()
+            .field("a")
+            .unwrap()
+            .argument("x")
+            .unwrap()
+            .default_value(),
+        Some(&Value::String(String::from("\u{1F600}")))
+    );
+}
+
+#[test]
+fn parse_value_string_lone_surrogate_fails() {
+    assert!(parse_Value(r#""\uD83D""#).is_err());
+    assert!(parse_Value(r#""\uD83Dx""#).is_err());
+    assert!(parse_Value(r#""\uD83D\u0041""#).is_err());
+    assert!(parse_Value(r#""\uDE00""#).is_err());
+    assert!(parse_Value(r#""\uDE00\uD83D""#).is_err());
+}
+
+#[test]
+fn parse_value_int_out_of_range_fails() {
+    assert!(parse_Value("99999999999999999999").is_err());
+}
+
+#[test]
+fn parse_value_list_and_object() {
+    assert_eq!(
+        parse_Value(r#"{tags: ["a" "b"] limit: 10 nested: {empty: []}}"#).unwrap(),
+        Value::Object(vec![
+            (
+                String::from("tags"),
+                Value::List(vec![
+                    Value::String(String::from("a")),
+                    Value::String(String::from("b")),
+                ]),
+            ),
+            (String::from("limit"), Value::Int(10)),
+            (
+                String::from("nested"),
+                Value::Object(vec![(String::from("empty"), Value::List(vec![]))]),
+            ),
+        ])
+    );
+}
+
+#[test]
+fn parse_value_with_garbage_fails() {
+    assert!(parse_Value("[1 2").is_err());
+    assert!(parse_Value("{a 1}").is_err());
+    assert!(parse_Value("1.").is_err());
+}
+
+#[test]
+fn parse_field_argument_literal_defaults() {
+    let t = parse_Field(r#"aaa:bbb (limit: Int = 10 filter: Filter = {tags: ["a"]})"#).unwrap();
+
+    assert_eq!(t.arguments.len(), 2);
+    assert_eq!(t.arguments[0].default, Option::Some(Value::Int(10)));
+    assert_eq!(
+        t.arguments[1].default,
+        Option::Some(Value::Object(vec![
+            (
+                String::from("tags"),
+                Value::List(vec![Value::String(String::from("a"))]),
+            ),
+        ]))
+    );
+}
+
 #[test]
 fn parse_field_no_arguments() {
     let t = parse_Field("aaa:bbb! @ccc").unwrap();
 
     assert_eq!(t.name, "aaa");
     assert_eq!(t.arguments, Vec::<Argument>::new());
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
 }
 
 #[test]
@@ -133,16 +426,12 @@ fn parse_field_one_argument_no_default() {
     let t = parse_Field("aaa:bbb! (ccc:ddd) @eee").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 1);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(a.argument_type, OuterType::named("ddd", true));
     assert!(a.default.is_none());
 }
 
@@ -151,17 +440,13 @@ fn parse_field_one_argument_with_default() {
     let t = parse_Field("aaa:bbb! (ccc:ddd = eee) @fff").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 1);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
-    assert_eq!(a.default, Option::Some(Value::new("eee")));
+    assert_eq!(a.argument_type, OuterType::named("ddd", true));
+    assert_eq!(a.default, Option::Some(Value::Enum(String::from("eee"))));
 }
 
 #[test]
@@ -169,26 +454,17 @@ fn parse_field_two_arguments_no_default() {
     let t = parse_Field("aaa:bbb! (ccc:ddd eee: [fff!]) @ggg").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 2);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(a.argument_type, OuterType::named("ddd", true));
     assert!(a.default.is_none());
 
     let a = &t.arguments[1];
     assert_eq!(a.name, "eee");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "fff");
-    assert_eq!(
-        a.argument_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
+    assert_eq!(a.argument_type, OuterType::list(OuterType::named("fff", false), true));
     assert!(a.default.is_none());
 }
 
@@ -197,23 +473,17 @@ fn parse_field_two_arguments_first_default() {
     let t = parse_Field("aaa:bbb! (ccc:ddd = eee fff: ggg!) @hhh").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 2);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
-    assert_eq!(a.default, Option::Some(Value::new("eee")));
+    assert_eq!(a.argument_type, OuterType::named("ddd", true));
+    assert_eq!(a.default, Option::Some(Value::Enum(String::from("eee"))));
 
     let a = &t.arguments[1];
     assert_eq!(a.name, "fff");
-    assert_eq!(a.argument_type.nullable, false);
-    assert_eq!(a.argument_type.inner.name, "ggg");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(a.argument_type, OuterType::named("ggg", false));
     assert!(a.default.is_none());
 }
 
@@ -222,27 +492,18 @@ fn parse_field_two_arguments_second_default() {
     let t = parse_Field("aaa:bbb! (ccc:[ddd] eee: fff! =ggg ) @hhh").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 2);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(
-        a.argument_type.inner.kind,
-        InnerTypeKind::Vector { nullable: true }
-    );
+    assert_eq!(a.argument_type, OuterType::list(OuterType::named("ddd", true), true));
     assert!(a.default.is_none());
 
     let a = &t.arguments[1];
     assert_eq!(a.name, "eee");
-    assert_eq!(a.argument_type.nullable, false);
-    assert_eq!(a.argument_type.inner.name, "fff");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
-    assert_eq!(a.default, Option::Some(Value::new("ggg")));
+    assert_eq!(a.argument_type, OuterType::named("fff", false));
+    assert_eq!(a.default, Option::Some(Value::Enum(String::from("ggg"))));
 }
 
 #[test]
@@ -250,27 +511,47 @@ fn parse_field_two_arguments_both_default() {
     let t = parse_Field("aaa:bbb! (ccc:[ddd!]!=eee fff: ggg=hhh) @iii").unwrap();
 
     assert_eq!(t.name, "aaa");
-    assert_eq!(t.field_type.nullable, false);
-    assert_eq!(t.field_type.inner.name, "bbb");
-    assert_eq!(t.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(t.field_type, OuterType::named("bbb", false));
     assert_eq!(t.arguments.len(), 2);
 
     let a = &t.arguments[0];
     assert_eq!(a.name, "ccc");
-    assert_eq!(a.argument_type.nullable, false);
-    assert_eq!(a.argument_type.inner.name, "ddd");
-    assert_eq!(
-        a.argument_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
-    assert_eq!(a.default, Option::Some(Value::new("eee")));
+    assert_eq!(a.argument_type, OuterType::list(OuterType::named("ddd", false), false));
+    assert_eq!(a.default, Option::Some(Value::Enum(String::from("eee"))));
 
     let a = &t.arguments[1];
     assert_eq!(a.name, "fff");
-    assert_eq!(a.argument_type.nullable, true);
-    assert_eq!(a.argument_type.inner.name, "ggg");
-    assert_eq!(a.argument_type.inner.kind, InnerTypeKind::NonVector);
-    assert_eq!(a.default, Option::Some(Value::new("hhh")));
+    assert_eq!(a.argument_type, OuterType::named("ggg", true));
+    assert_eq!(a.default, Option::Some(Value::Enum(String::from("hhh"))));
+}
+
+#[test]
+fn parse_field_arguments_before_type() {
+    let t = parse_Field("createUser(input: UserInput! type: Int = 1): User @eee").unwrap();
+
+    assert_eq!(t.name, "createUser");
+    assert_eq!(t.field_type, OuterType::named("User", true));
+    assert_eq!(t.arguments.len(), 2);
+    assert_eq!(t.arguments[0].name, "input");
+    assert_eq!(t.arguments[0].argument_type, OuterType::named("UserInput", false));
+    assert_eq!(t.arguments[1].name, "type");
+    assert_eq!(t.arguments[1].default, Option::Some(Value::Int(1)));
+}
+
+#[test]
+fn parse_field_argument_directives() {
+    let t = parse_Field("aaa(bbb: Int = 1 @ccc ddd: Int @eee @fff): ggg @hhh").unwrap();
+
+    assert_eq!(t.arguments[0].directives.len(), 1);
+    assert_eq!(t.arguments[0].directives[0].name, "ccc");
+    assert_eq!(t.arguments[1].directives.len(), 2);
+    assert_eq!(t.directives.len(), 1);
+    assert_eq!(t.directives[0].name, "hhh");
+}
+
+#[test]
+fn parse_field_empty_arguments_fails() {
+    assert!(parse_Field("aaa(): bbb").is_err());
 }
 
 #[test]
@@ -291,56 +572,32 @@ fn parse_interface_() {
 
     let f = &t.fields[0];
     assert_eq!(f.name, "var1");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type1");
-    assert_eq!(f.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(f.field_type, OuterType::named("type1", true));
 
     let f = &t.fields[1];
     assert_eq!(f.name, "var2");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type2");
-    assert_eq!(f.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(f.field_type, OuterType::named("type2", false));
 
     let f = &t.fields[2];
     assert_eq!(f.name, "var3");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type3");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: true }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type3", true), true));
 
     let f = &t.fields[3];
     assert_eq!(f.name, "var4");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type4");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type4", false), true));
 
     let f = &t.fields[4];
     assert_eq!(f.name, "var5");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type5");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: true }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type5", true), false));
 
     let f = &t.fields[5];
     assert_eq!(f.name, "var6");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type6");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type6", false), false));
 }
 
 #[test]
-fn parse_interface_directives_ignored() {
-    let t = parse_Interface("interface Inter{
+fn parse_interface_directives_kept() {
+    let t = parse_Interface("interface Inter @key(fields: \"id\") {
         var1: type1 @directive1
         var2: type2! @directive2(aaa2: bbb2)
         var3: [type3] @directive3(aaa3: bbb3 ccc3: ddd3)
@@ -351,7 +608,13 @@ fn parse_interface_directives_ignored() {
         var7: type7 @directive11 @directive12
         }").unwrap();
 
-    assert_eq!(t.fields.len(), 7)
+    assert_eq!(t.fields.len(), 7);
+    assert_eq!(t.directives.len(), 1);
+    assert_eq!(t.directives[0].name, "key");
+    assert_eq!(t.fields[0].directives.len(), 1);
+    assert_eq!(t.fields[5].directives.len(), 3);
+    assert_eq!(t.fields[5].directives[2].name, "directive10");
+    assert_eq!(t.fields[5].directives[2].arguments.len(), 3);
 }
 
 #[test]
@@ -373,51 +636,27 @@ fn parse_type_() {
 
     let f = &t.fields[0];
     assert_eq!(f.name, "var1");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type1");
-    assert_eq!(f.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(f.field_type, OuterType::named("type1", true));
 
     let f = &t.fields[1];
     assert_eq!(f.name, "var2");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type2");
-    assert_eq!(f.field_type.inner.kind, InnerTypeKind::NonVector);
+    assert_eq!(f.field_type, OuterType::named("type2", false));
 
     let f = &t.fields[2];
     assert_eq!(f.name, "var3");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type3");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: true }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type3", true), true));
 
     let f = &t.fields[3];
     assert_eq!(f.name, "var4");
-    assert_eq!(f.field_type.nullable, true);
-    assert_eq!(f.field_type.inner.name, "type4");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type4", false), true));
 
     let f = &t.fields[4];
     assert_eq!(f.name, "var5");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type5");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: true }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type5", true), false));
 
     let f = &t.fields[5];
     assert_eq!(f.name, "var6");
-    assert_eq!(f.field_type.nullable, false);
-    assert_eq!(f.field_type.inner.name, "type6");
-    assert_eq!(
-        f.field_type.inner.kind,
-        InnerTypeKind::Vector { nullable: false }
-    );
+    assert_eq!(f.field_type, OuterType::list(OuterType::named("type6", false), false));
 }
 
 #[test]
@@ -456,6 +695,18 @@ fn parse_type_implements_one() {
     assert_eq!(t.fields.len(), 6);
 }
 
+#[test]
+fn parse_implements_with_ampersand() {
+    let t = parse_Type("type Type implements & Node & Entity { id: ID! }").unwrap();
+    assert_eq!(t.implements, vec!["Node", "Entity"]);
+
+    let i = parse_Interface("interface Named implements Node & Entity @cached { id: ID! }").unwrap();
+    assert_eq!(i.implements, vec!["Node", "Entity"]);
+    assert_eq!(i.directives.len(), 1);
+
+    assert!(parse_Interface("interface Named implements Node & { id: ID! }").is_err());
+}
+
 #[test]
 fn parse_type_implements_error() {
     let t = parse_Type(
@@ -468,8 +719,8 @@ fn parse_type_implements_error() {
 }
 
 #[test]
-fn parse_type_directives_ignored() {
-    let t = parse_Type("type typ implements inter1 inter2{
+fn parse_type_directives_kept() {
+    let t = parse_Type("type typ implements inter1 inter2 @entity @cache(maxAge: 60) {
         var1: type1 @directive1
         var2: type2! @directive2(aaa2: bbb2)
         var3: [type3] @directive3(aaa3: bbb3 ccc3: ddd3)
@@ -481,7 +732,15 @@ fn parse_type_directives_ignored() {
         }").unwrap();
 
     assert_eq!(t.implements.len(), 2);
-    assert_eq!(t.fields.len(), 7)
+    assert_eq!(t.fields.len(), 7);
+    assert_eq!(t.directives.len(), 2);
+    assert_eq!(t.directives[1].argument("maxAge"), Option::Some(&Value::Int(60)));
+    assert_eq!(t.fields[6].directives.len(), 2);
+    assert_eq!(t.fields[6].directives[1].name, "directive12");
+}
+
+fn enum_value_names(t: &Enum) -> Vec<&str> {
+    t.values.iter().map(|v| v.name.as_str()).collect()
 }
 
 #[test]
@@ -492,7 +751,7 @@ fn parse_type_enum_zero_values() {
     ).unwrap();
 
     assert_eq!(t.name, "Enu");
-    assert_eq!(t.values, Vec::<String>::new());
+    assert!(t.values.is_empty());
 }
 
 #[test]
@@ -504,7 +763,7 @@ fn parse_type_enum_one_value() {
     ).unwrap();
 
     assert_eq!(t.name, "Enu");
-    assert_eq!(t.values, vec!["enu"]);
+    assert_eq!(enum_value_names(&t), vec!["enu"]);
 }
 
 #[test]
@@ -517,7 +776,7 @@ fn parse_type_enum_three_values() {
     ).unwrap();
 
     assert_eq!(t.name, "Enu");
-    assert_eq!(t.values, vec!["enu1", "enu2", "enu3"]);
+    assert_eq!(enum_value_names(&t), vec!["enu1", "enu2", "enu3"]);
 }
 
 #[test]
@@ -530,7 +789,30 @@ fn parse_type_enum_two_values() {
     ).unwrap();
 
     assert_eq!(t.name, "Enu");
-    assert_eq!(t.values, vec!["enu1", "enu2"]);
+    assert_eq!(enum_value_names(&t), vec!["enu1", "enu2"]);
+}
+
+#[test]
+fn parse_type_enum_value_directives() {
+    let t = parse_Enum(
+        "enum Enu @flags {
+        enu1 @deprecated(reason: \"old\")
+        enu2
+        }",
+    ).unwrap();
+
+    assert_eq!(t.directives.len(), 1);
+    assert_eq!(t.values[0].directives.len(), 1);
+    assert_eq!(t.values[0].directives[0].name, "deprecated");
+    assert!(t.values[1].directives.is_empty());
+}
+
+#[test]
+fn parse_union_directives() {
+    let t = parse_Union("union un @dir(a: 1) = uni1 | uni2").unwrap();
+
+    assert_eq!(t.directives.len(), 1);
+    assert_eq!(t.members, vec!["uni1", "uni2"]);
 }
 
 #[test]
@@ -566,3 +848,1564 @@ fn parse_union_no_members_fails() {
 fn parse_union_no_pipe_fails() {
     assert!(parse_Union("union un = uni1 uni2").is_err());
 }
+
+#[test]
+fn parse_input_() {
+    let t = parse_Input(
+        "input UserInput {
+        name: String!
+        tags: [String!] = [\"a\"]
+        input: NestedInput
+        }",
+    ).unwrap();
+
+    assert_eq!(t.name, "UserInput");
+    assert_eq!(t.fields.len(), 3);
+    assert_eq!(t.fields[0].name, "name");
+    assert_eq!(t.fields[0].argument_type, OuterType::named("String", false));
+    assert_eq!(t.fields[1].name, "tags");
+    assert_eq!(
+        t.fields[1].default,
+        Option::Some(Value::List(vec![Value::String(String::from("a"))]))
+    );
+    assert_eq!(t.fields[2].name, "input");
+}
+
+#[test]
+fn parse_input_with_arguments_fails() {
+    assert!(parse_Input("input UserInput { name(a: Int): String }").is_err());
+}
+
+#[test]
+fn process_input_() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { createUser(input: UserInput!): User }
+        type User { name: String }
+        enum Role { ADMIN USER }
+        input UserInput { name: String! role: Role address: AddressInput }
+        input AddressInput { city: String }",
+    )).unwrap();
+
+    let inputs: Vec<String> = processed
+        .inputs()
+        .into_iter()
+        .map(|i| i.name.to_owned())
+        .collect();
+    assert_eq!(inputs, vec!["AddressInput", "UserInput"]);
+
+    let user_input = processed.inputs().into_iter().nth(1).unwrap();
+    let fields: Vec<String> = user_input
+        .fields()
+        .into_iter()
+        .map(|f| f.name.to_owned())
+        .collect();
+    assert_eq!(fields, vec!["name", "role", "address"]);
+
+    let sorted: Vec<String> = user_input
+        .sorted_fields()
+        .into_iter()
+        .map(|f| f.name.to_owned())
+        .collect();
+    assert_eq!(sorted, vec!["address", "name", "role"]);
+}
+
+#[test]
+fn process_input_duplicate_field_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { user: String }
+        input UserInput { name: String name: Int }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors, vec!["Input UserInput has duplicate field named name"]);
+}
+
+#[test]
+fn process_input_wrong_positions_fail() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { user(filter: User): UserInput }
+        type User { name: String }
+        input UserInput { friend: User }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].contains("uses return type UserInput, but UserInput is input"));
+    assert!(errors[1].contains("argument filter references type User, but User is type"));
+    assert!(errors[2].contains("Input UserInput field friend references type User"));
+}
+
+#[test]
+fn parse_scalar_() {
+    let t = parse_Scalar("scalar DateTime").unwrap();
+
+    assert_eq!(t.name, "DateTime");
+    assert!(t.specified_by_url().is_none());
+}
+
+#[test]
+fn parse_scalar_specified_by() {
+    let t = parse_Scalar(r#"scalar URL @specifiedBy(url: "https://tools.ietf.org/html/rfc3986")"#)
+        .unwrap();
+
+    assert_eq!(t.name, "URL");
+    assert_eq!(
+        t.specified_by_url(),
+        Option::Some(&String::from("https://tools.ietf.org/html/rfc3986"))
+    );
+}
+
+#[test]
+fn process_scalar_() {
+    let processed = parse_gsdl(String::from(
+        r#"scheme { query: Query }
+        type Query { now: DateTime! id: ID tag(value: JSON): JSON }
+        scalar JSON
+        scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")"#,
+    )).unwrap();
+
+    let scalars: Vec<(String, Option<String>)> = processed
+        .scalars()
+        .into_iter()
+        .map(|s| (s.name.to_owned(), s.specified_by_url.cloned()))
+        .collect();
+    assert_eq!(
+        scalars,
+        vec![
+            (
+                String::from("DateTime"),
+                Option::Some(String::from(
+                    "https://scalars.graphql.org/andimarek/date-time"
+                )),
+            ),
+            (String::from("JSON"), Option::None),
+        ]
+    );
+}
+
+#[test]
+fn process_scalar_redefines_builtin_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { id: ID }
+        scalar ID",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("Scalar ID is already defined as Builtin(ID)"));
+}
+
+#[test]
+fn process_scalar_bad_specified_by_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { now: DateTime }
+        scalar DateTime @specifiedBy(url: 1) @specifiedBy(url: \"a\")",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            "Scalar DateTime uses directive @specifiedBy 2 times, but @specifiedBy is not repeatable",
+            "Scalar DateTime uses directive @specifiedBy with invalid argument url: expected String!, found 1",
+        ]
+    );
+}
+
+#[test]
+fn process_schema_entry_points() {
+    let processed = parse_gsdl(String::from(
+        "directive @directive on SCHEMA
+        schema @directive { query: Q mutation: M subscription: S }
+        type Q { a: Int }
+        type M { b: Int }
+        type S { c: Int }",
+    )).unwrap();
+
+    assert_eq!(*processed.query().name, "Q");
+    assert_eq!(*processed.mutate().unwrap().name, "M");
+    assert_eq!(*processed.subscription().unwrap().name, "S");
+}
+
+#[test]
+fn process_scheme_legacy_entry_points() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Q mutate: M }
+        type Q { a: Int }
+        type M { b: Int }",
+    )).unwrap();
+
+    assert_eq!(*processed.query().name, "Q");
+    assert_eq!(*processed.mutate().unwrap().name, "M");
+    assert!(processed.subscription().is_none());
+}
+
+#[test]
+fn process_implicit_entry_points() {
+    let processed = parse_gsdl(String::from(
+        "type Query { a: Int }
+        type Subscription { c: Int }",
+    )).unwrap();
+
+    assert_eq!(*processed.query().name, "Query");
+    assert!(processed.mutate().is_none());
+    assert_eq!(*processed.subscription().unwrap().name, "Subscription");
+}
+
+#[test]
+fn process_implicit_entry_points_without_query_fails() {
+    let errors = parse_gsdl(String::from("type Mutation { a: Int }"))
+        .err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors, vec!["Scheme query entry point Query type is not defined"]);
+}
+
+#[test]
+fn process_schema_entry_point_errors() {
+    let errors = parse_gsdl(String::from(
+        "schema { query: Q mutation: M mutate: M subscription: E unknown: Q }
+        type Q { a: Int }
+        type M { b: Int }
+        enum E { A }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            "Duplicate mutate entry in scheme: old M, new M",
+            "Unknown entry unknown in scheme",
+        ]
+    );
+
+    let errors = parse_gsdl(String::from(
+        "schema { query: Q mutation: M subscription: E }
+        type Q { a: Int }
+        enum E { A }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0], "Scheme mutation entry point M type is not defined");
+    assert!(errors[1].starts_with("Scheme subscription entry point E is not a type"));
+}
+
+#[test]
+fn process_directives_() {
+    let processed = parse_gsdl(String::from(
+        "directive @private on SCHEMA
+        directive @key(fields: String) on OBJECT | UNION
+        directive @limit(max: Int!) on ARGUMENT_DEFINITION
+        directive @auth(role: Role!) on FIELD_DEFINITION
+        directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+        directive @entity on INTERFACE
+        directive @flags on ENUM
+        directive @internal on ENUM_VALUE
+        directive @oneOf on INPUT_OBJECT
+        directive @since(version: Int) on INPUT_FIELD_DEFINITION
+        directive @format(pattern: String!) on SCALAR
+        schema @private { query: Query }
+        type Query @key(fields: \"id\") {
+            users(first: Int @limit(max: 100)): [User] @auth(role: ADMIN) @cacheControl(maxAge: 60)
+        }
+        interface Node @entity { id: ID }
+        union User @key = Query
+        enum Role @flags { ADMIN @internal USER }
+        input Filter @oneOf { id: ID @since(version: 2) }
+        scalar Date @format(pattern: \"YYYY\")",
+    )).unwrap();
+
+    let query = processed.query();
+    let key = query.directive("key").unwrap();
+    assert_eq!(*key.name, "key");
+    assert_eq!(
+        key.argument("fields"),
+        Option::Some(&Value::String(String::from("id")))
+    );
+    assert!(query.directive("auth").is_none());
+
+    let field = query.fields().into_iter().next().unwrap();
+    let directives: Vec<String> = field
+        .directives()
+        .into_iter()
+        .map(|d| d.name.to_owned())
+        .collect();
+    assert_eq!(directives, vec!["auth", "cacheControl"]);
+    let auth = field.directive("auth").unwrap();
+    let arguments: Vec<(&String, &Value)> = auth.arguments().collect();
+    assert_eq!(
+        arguments,
+        vec![(&String::from("role"), &Value::Enum(String::from("ADMIN")))]
+    );
+    let argument = field.arguments().into_iter().next().unwrap();
+    assert_eq!(
+        argument.directive("limit").unwrap().argument("max"),
+        Option::Some(&Value::Int(100))
+    );
+
+    let interface = processed.interfaces().into_iter().next().unwrap();
+    assert!(interface.directive("entity").is_some());
+    let union = processed.unions().into_iter().next().unwrap();
+    assert!(union.directive("key").is_some());
+    let role = processed.enums().into_iter().next().unwrap();
+    assert!(role.directive("flags").is_some());
+    let admin_directives = role.value_directives("ADMIN").unwrap();
+    assert!(admin_directives.get("internal").is_some());
+    assert_eq!(role.value_directives("USER").unwrap().into_iter().count(), 0);
+    assert!(role.value_directives("GUEST").is_none());
+    let filter = processed.inputs().into_iter().next().unwrap();
+    assert!(filter.directive("oneOf").is_some());
+    let id = filter.fields().into_iter().next().unwrap();
+    assert!(id.directive("since").is_some());
+    let date = processed.scalars().into_iter().next().unwrap();
+    assert!(date.directive("format").is_some());
+    assert!(processed.scheme_directives().get("private").is_some());
+}
+
+#[test]
+fn parse_directive_definition() {
+    let d = parse_DirectiveDefinition(
+        "directive @cache(maxAge: Int = 60) repeatable on | FIELD_DEFINITION | OBJECT",
+    ).unwrap();
+    assert_eq!(d.name, "cache");
+    assert!(d.repeatable);
+    assert_eq!(
+        d.locations,
+        vec![DirectiveLocation::FIELD_DEFINITION, DirectiveLocation::OBJECT]
+    );
+    assert_eq!(d.arguments.len(), 1);
+    assert_eq!(d.arguments[0].default, Option::Some(Value::Int(60)));
+
+    let d = parse_DirectiveDefinition("directive @internal on SCHEMA").unwrap();
+    assert!(!d.repeatable);
+    assert!(d.arguments.is_empty());
+}
+
+#[test]
+fn parse_directive_definition_unknown_location_fails() {
+    assert!(parse_DirectiveDefinition("directive @internal on SCHEME").is_err());
+    assert!(parse_DirectiveDefinition("directive @internal").is_err());
+}
+
+#[test]
+fn process_directive_definitions() {
+    let processed = parse_gsdl(String::from(
+        "directive @tag(name: String!) repeatable on OBJECT
+        type Query @tag(name: \"a\") @tag(name: \"b\") { a: Int @deprecated }",
+    )).unwrap();
+
+    let names: Vec<&str> = processed
+        .directive_definitions()
+        .into_iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec!["deprecated", "include", "skip", "specifiedBy", "tag"]
+    );
+    let tag = processed.directive_definition("tag").unwrap();
+    assert!(tag.repeatable);
+    assert_eq!(tag.locations, &[DirectiveLocation::OBJECT]);
+    assert_eq!(tag.arguments().into_iter().count(), 1);
+    assert!(processed.directive_definition("unknown").is_none());
+}
+
+#[test]
+fn process_directive_usage_errors() {
+    let errors = parse_gsdl(String::from(
+        "directive @limit(max: Int!, filter: Filter) on FIELD_DEFINITION
+        enum Level { LOW HIGH }
+        input Filter { level: Level! }
+        type Query @unknown @limit(max: 1) {
+            a: Int @limit
+            b: Int @limit(max: 1, min: 0)
+            c: Int @limit(max: 1, filter: { level: MEDIUM })
+            d: Int @limit(max: 1) @limit(max: 2)
+        }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(
+        errors,
+        vec![
+            "Type Query field a uses directive @limit without required argument max",
+            "Type Query field b uses directive @limit with argument min, but @limit has no such argument",
+            "Type Query field c uses directive @limit with invalid argument filter: field level of Filter: expected Level!, found MEDIUM",
+            "Type Query field d uses directive @limit 2 times, but @limit is not repeatable",
+            "Type Query uses directive @unknown, but @unknown is not defined",
+            "Type Query uses directive @limit, but @limit is not allowed on OBJECT",
+        ]
+    );
+}
+
+#[test]
+fn process_directive_redefines_builtin_fails() {
+    let errors = parse_gsdl(String::from(
+        "directive @deprecated on OBJECT
+        type Query { a: Int }",
+    )).err()
+        .map(messages)
+        .unwrap();
+
+    assert_eq!(errors, vec!["Directive @deprecated is already defined"]);
+}
+
+#[test]
+fn parse_descriptions() {
+    let t = parse_Type(
+        "\"Root, with #hash\" type Query {
+            \"\"\"
+            Users list
+            \"\"\"
+            users(\"max count\" first: Int = 10): [String]
+        }",
+    ).unwrap();
+    assert_eq!(t.description, Option::Some(String::from("Root, with #hash")));
+    assert_eq!(t.fields[0].description, Option::Some(String::from("Users list")));
+    assert_eq!(
+        t.fields[0].arguments[0].description,
+        Option::Some(String::from("max count"))
+    );
+
+    let e = parse_Enum("enum Level { \"lowest\" LOW HIGH }").unwrap();
+    assert_eq!(e.description, None);
+    assert_eq!(e.values[0].description, Option::Some(String::from("lowest")));
+    assert_eq!(e.values[1].description, None);
+
+    let d = parse_DirectiveDefinition("\"\"\"Cache\"\"\" directive @cache on OBJECT").unwrap();
+    assert_eq!(d.description, Option::Some(String::from("Cache")));
+}
+
+#[test]
+fn parse_block_string_value() {
+    assert_eq!(
+        parse_Value("\"\"\"\n    Hello,\n      World!\n\n    Yours, \\\"\"\"GraphQL\\\"\"\".\n  \"\"\"").unwrap(),
+        Value::String(String::from("Hello,\n  World!\n\nYours, \"\"\"GraphQL\"\"\"."))
+    );
+    assert_eq!(
+        parse_Value("\"\"\"  first line\n    second\"\"\"").unwrap(),
+        Value::String(String::from("  first line\nsecond"))
+    );
+    assert_eq!(
+        parse_Value("\"\"\"\\n is not escaped\"\"\"").unwrap(),
+        Value::String(String::from("\\n is not escaped"))
+    );
+}
+
+#[test]
+fn process_descriptions() {
+    let processed = parse_gsdl(String::from(
+        "\"\"\"
+        The schema, # not a comment
+        with commas
+        \"\"\"
+        schema { query: Query }
+        \"Root\" type Query { \"count, # not a comment\" count(\"min\" from: Int): Level }
+        \"Levels\" enum Level { \"lowest\" LOW HIGH } # a comment \"with quotes\"
+        \"Date\" scalar Date
+        \"Filter\" input Filter { \"exact\" at: Date }
+        \"Node\" interface Node { \"id\" id: ID }
+        \"Any\" union Any = Query
+        \"Tag\" directive @tag on OBJECT",
+    )).unwrap();
+
+    assert_eq!(
+        processed.scheme_description().unwrap(),
+        "The schema, # not a comment\nwith commas"
+    );
+    let query = processed.query();
+    assert_eq!(query.description().unwrap(), "Root");
+    let field = query.fields().into_iter().next().unwrap();
+    assert_eq!(field.description().unwrap(), "count, # not a comment");
+    let argument = field.arguments().into_iter().next().unwrap();
+    assert_eq!(argument.description().unwrap(), "min");
+
+    let level = processed.enums().into_iter().next().unwrap();
+    assert_eq!(level.description().unwrap(), "Levels");
+    assert_eq!(level.value_description("LOW").unwrap().unwrap(), "lowest");
+    assert_eq!(level.value_description("HIGH").unwrap(), None);
+    assert!(level.value_description("MEDIUM").is_none());
+
+    let scalar = processed.scalars().into_iter().next().unwrap();
+    assert_eq!(scalar.description().unwrap(), "Date");
+    let input = processed.inputs().into_iter().next().unwrap();
+    assert_eq!(input.description().unwrap(), "Filter");
+    let at = input.fields().into_iter().next().unwrap();
+    assert_eq!(at.description().unwrap(), "exact");
+    let node = processed.interfaces().into_iter().next().unwrap();
+    assert_eq!(node.description().unwrap(), "Node");
+    let union = processed.unions().into_iter().next().unwrap();
+    assert_eq!(union.description().unwrap(), "Any");
+    assert_eq!(
+        processed.directive_definition("tag").unwrap().description().unwrap(),
+        "Tag"
+    );
+    assert!(
+        processed
+            .directive_definition("skip")
+            .unwrap()
+            .description()
+            .is_none()
+    );
+}
+
+#[test]
+fn process_duplicate_diagnostic_points_to_both_definitions() {
+    let errors = parse_gsdl(String::from(
+        "type Query { a: Int }\n# type Query, again\ntype  Query { b: Int }",
+    )).err()
+        .unwrap();
+
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error.code, Code::DuplicateDefinition);
+    let span = error.span.unwrap();
+    assert_eq!((span.line, span.column), (3, 7));
+    assert_eq!(error.labels.len(), 1);
+    assert_eq!(error.labels[0].message, "first defined here");
+    assert_eq!(
+        (error.labels[0].span.line, error.labels[0].span.column),
+        (1, 6)
+    );
+}
+
+#[test]
+fn parse_syntax_error_position() {
+    let errors = parse_gsdl(String::from(
+        "# comment, with commas, and \"quotes\"\ntype Query { a: Int, b }",
+    )).err()
+        .unwrap();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, Code::Syntax);
+    assert!(errors[0].message.starts_with("Unexpected token `}`"));
+    let span = errors[0].span.unwrap();
+    assert_eq!((span.line, span.column), (2, 24));
+
+    let errors = parse_gsdl(String::from("type Query {\n  a: Int @limit(max: 99999999999999999999)\n}"))
+        .err()
+        .unwrap();
+    assert_eq!(
+        messages(errors.clone()),
+        vec!["Int value 99999999999999999999 is out of range"]
+    );
+    let span = errors[0].span.unwrap();
+    assert_eq!((span.line, span.column), (2, 22));
+}
+
+#[test]
+fn render_diagnostic() {
+    let source = "directive @tag on OBJECT\ntype Query @tag @tag { a: Int }";
+    let errors = parse_gsdl(String::from(source)).err().unwrap();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "2:12: error: Type Query uses directive @tag 2 times, but @tag is not repeatable"
+    );
+    assert_eq!(
+        errors[0].render(source),
+        "error[invalid-directive]: Type Query uses directive @tag 2 times, but @tag is not repeatable
+  --> 2:12
+  |
+2 | type Query @tag @tag { a: Int }
+  |            ^^^^
+  ::: 2:17
+  |
+2 | type Query @tag @tag { a: Int }
+  |                 ---- used again here
+"
+    );
+}
+
+#[test]
+fn lex_ignored_tokens() {
+    let tokens: Vec<Tok> = Lexer::new("\u{FEFF}type, # comment, \"quoted\"\r\n  Query{a:[Int!]}")
+        .map(|token| token.unwrap().1)
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            Tok::Type,
+            Tok::Name("Query"),
+            Tok::LeftBrace,
+            Tok::Name("a"),
+            Tok::Colon,
+            Tok::LeftBracket,
+            Tok::Name("Int"),
+            Tok::Bang,
+            Tok::RightBracket,
+            Tok::RightBrace,
+        ]
+    );
+    let offsets: Vec<(usize, usize)> = Lexer::new("type  Query")
+        .map(|token| {
+            let (start, _, end) = token.unwrap();
+            (start, end)
+        })
+        .collect();
+    assert_eq!(offsets, vec![(0, 4), (6, 11)]);
+}
+
+#[test]
+fn lex_strings_keep_commas_and_comments() {
+    assert_eq!(
+        parse_Value("\"a, b # c\"").unwrap(),
+        Value::String(String::from("a, b # c"))
+    );
+    assert_eq!(
+        parse_Value("[\"#\", \",\"]").unwrap(),
+        Value::List(vec![
+            Value::String(String::from("#")),
+            Value::String(String::from(",")),
+        ])
+    );
+}
+
+#[test]
+fn lex_errors() {
+    let errors = parse_gsdl(String::from("type Query {\n  a: Int @tag(name: \"open)\n}"))
+        .err()
+        .unwrap();
+    assert_eq!(messages(errors.clone()), vec!["Unterminated string"]);
+    let span = errors[0].span.unwrap();
+    assert_eq!((span.line, span.column), (2, 21));
+
+    assert!(parse_Value("0123").is_err());
+    assert!(parse_Value("123abc").is_err());
+    assert!(parse_Value("1.").is_err());
+    assert!(parse_Value("1e").is_err());
+    assert!(parse_Value("\"\"\"open").is_err());
+    assert!(parse_Type("type Query { a: Int ? }").is_err());
+}
+
+#[test]
+fn process_covariant_implementation() {
+    assert!(parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID node: Node owner: Owner friends(first: Int): [Node] }
+        union Owner = User | Query
+        type User implements Node {
+            id: ID!
+            node: User!
+            owner: User
+            friends(first: Int after: String order: Int! = 1): [User!]!
+        }
+        type Query { user: User }",
+    )).is_ok());
+}
+
+#[test]
+fn process_invalid_implementation_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID! node: Node friends(first: Int! last: Int): [Node] }
+        type User implements Node {
+            id: ID
+            node: [User]
+            friends(first: Int after: String!): [Query!]
+        }
+        type Query { user: User }",
+    )).err()
+        .unwrap();
+
+    assert!(errors.iter().all(|e| e.code == Code::InterfaceMismatch));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type User implements Node, but field id returns ID, \
+             which is not compatible with ID! in interface",
+            "Type User implements Node, but field node returns [User], \
+             which is not compatible with Node in interface",
+            "Type User implements Node, but field friends returns [Query!], \
+             which is not compatible with [Node] in interface",
+            "Type User implements Node, but field friends argument first has type Int, \
+             while interface has Int!",
+            "Type User implements Node, but field friends does not have argument last of interface",
+            "Type User implements Node, but field friends has additional required argument \
+             after: String!",
+        ]
+    );
+}
+
+#[test]
+fn process_interface_implements_interface() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID! }
+        interface Resource implements Node { id: ID! url: String parent: Resource }
+        type Image implements Node & Resource { id: ID! url: String! parent: Image }
+        type Query { node: Node }",
+    )).unwrap();
+
+    let resource = processed.get_interface("Resource").unwrap();
+    let implements: Vec<String> = resource
+        .implements()
+        .into_iter()
+        .map(|i| i.name.to_owned())
+        .collect();
+    assert_eq!(implements, vec!["Node"]);
+
+    let node = processed.get_interface("Node").unwrap();
+    let implementations: Vec<String> = node
+        .implementations()
+        .iter()
+        .map(|i| i.name().to_owned())
+        .collect();
+    assert_eq!(implementations, vec!["Image", "Resource"]);
+}
+
+#[test]
+fn process_interface_implementation_errors() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID! }
+        interface Resource implements Node { id: ID! }
+        interface Named implements Named { name: String }
+        interface A implements B { id: ID }
+        interface B implements A { id: ID }
+        type Image implements Resource { id: ID! }
+        type Query { node: Node }",
+    )).err()
+        .unwrap();
+
+    assert!(errors.iter().all(|e| e.code == Code::InterfaceMismatch));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type Image implements Resource, but not Node, which Resource implements",
+            "Interface A implements itself through A -> B -> A",
+            "Interface B implements itself through B -> A -> B",
+            "Interface Named implements itself through Named -> Named",
+        ]
+    );
+}
+
+#[test]
+fn process_union_member_errors() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Role { ADMIN }
+        union Bad = Query | Role | Missing | Any
+        union Any = Query
+        type Query { bad: Bad }",
+    )).err()
+        .unwrap();
+
+    assert_eq!(
+        errors.iter().map(|e| e.code).collect::<Vec<_>>(),
+        vec![Code::WrongKind, Code::UndefinedReference, Code::WrongKind]
+    );
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Union Bad includes Role, but Role is enum and only types can be union members",
+            "Union Bad includes Missing, but Missing is not defined",
+            "Union Bad includes Any, but Any is union and only types can be union members",
+        ]
+    );
+}
+
+#[test]
+fn process_possible_types() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! }
+        type Post implements Node { id: ID! }
+        union Result = User | Query
+        type Query { node: Node result: Result }",
+    )).unwrap();
+
+    let names = |name| -> Vec<String> {
+        processed
+            .possible_types(name)
+            .unwrap()
+            .iter()
+            .map(|t| t.name.to_owned())
+            .collect()
+    };
+    assert_eq!(names("Result"), vec!["User", "Query"]);
+    assert_eq!(names("Node"), vec!["Post", "User"]);
+
+    assert_eq!(
+        processed.possible_types("Query").err().unwrap().to_string(),
+        "Expected Query to be union or interface, but it is type"
+    );
+    assert_eq!(
+        processed.possible_types("Missing").err(),
+        Some(LookupError::NotFound(String::from("Missing")))
+    );
+}
+
+#[test]
+fn parse_extensions() {
+    match parse_Extension("extend type Query implements Node & Entity @key { id: ID! }").unwrap() {
+        Extension::Type(t) => {
+            assert_eq!(t.name, "Query");
+            assert_eq!(t.implements, vec!["Node", "Entity"]);
+            assert_eq!(t.directives.len(), 1);
+            assert_eq!(t.fields.len(), 1);
+        }
+        other => panic!("unexpected extension {:?}", other),
+    }
+    match parse_Extension("extend union Any @tag = User | Post").unwrap() {
+        Extension::Union(u) => assert_eq!(u.members, vec!["User", "Post"]),
+        other => panic!("unexpected extension {:?}", other),
+    }
+    match parse_Extension("extend schema { subscription: Subscription }").unwrap() {
+        Extension::Scheme(s) => assert_eq!(s.entries.len(), 1),
+        other => panic!("unexpected extension {:?}", other),
+    }
+    assert!(parse_Extension("extend enum Role { GUEST }").is_ok());
+    assert!(parse_Extension("extend input Page @deprecated").is_ok());
+    assert!(parse_Extension("extend interface Node implements Entity").is_ok());
+    assert!(parse_Extension("extend scalar Date @specifiedBy(url: \"x\")").is_ok());
+    assert!(parse_Extension("\"description\" extend type Query { a: Int }").is_err());
+}
+
+#[test]
+fn parse_empty_extension_fails() {
+    let errors = parse_gsdl(String::from(
+        "type Query { a: Int }
+        extend type Query",
+    )).err()
+        .unwrap();
+    assert_eq!(errors[0].code, Code::Syntax);
+    assert_eq!(errors[0].message, "Extension of type Query has to add something");
+}
+
+#[test]
+fn process_extensions_merged() {
+    let processed = parse_gsdl(String::from(
+        "extend type Query implements Node { user: User }
+        directive @tag on OBJECT | SCALAR
+        scheme { query: Query }
+        interface Node { id: ID! }
+        type Query { id: ID! }
+        type User { name: String }
+        type Post { title: String }
+        extend type User @tag { age: Int }
+        enum Role { ADMIN }
+        extend enum Role { USER }
+        union Any = User
+        extend union Any = Post
+        input Page { size: Int }
+        extend input Page { after: String }
+        scalar Date
+        extend scalar Date @tag
+        type Subscription { users: User }
+        extend schema { subscription: Subscription }",
+    )).unwrap();
+
+    let query = processed.query();
+    let fields: Vec<String> = query.fields().into_iter().map(|f| f.name.to_owned()).collect();
+    assert_eq!(fields, vec!["id", "user"]);
+    assert_eq!(query.implements().into_iter().count(), 1);
+
+    let user = processed.get_type("User").unwrap();
+    assert!(user.field("age").is_some());
+    assert!(user.directive("tag").is_some());
+
+    let role = processed.get_enum("Role").unwrap();
+    assert!(role.value("USER").is_some());
+    assert_eq!(processed.get_union("Any").unwrap().members().into_iter().count(), 2);
+    assert!(processed.get_input("Page").unwrap().field("after").is_some());
+    assert!(processed.get_scalar("Date").unwrap().directive("tag").is_some());
+    assert_eq!(*processed.subscription().unwrap().name, "Subscription");
+}
+
+#[test]
+fn process_extension_errors() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { id: ID! }
+        enum Role { ADMIN }
+        extend type Query { id: ID }
+        extend type Missing { a: Int }
+        extend type Role { a: Int }
+        extend enum Role { ADMIN }
+        extend scalar Int @deprecated
+        extend schema { query: Role }",
+    )).err()
+        .unwrap();
+
+    assert_eq!(
+        errors.iter().map(|e| e.code).collect::<Vec<_>>(),
+        vec![
+            Code::DuplicateDefinition,
+            Code::UndefinedReference,
+            Code::WrongKind,
+            Code::DuplicateDefinition,
+            Code::WrongKind,
+            Code::DuplicateDefinition,
+        ]
+    );
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Extension of type Query adds field id, which is already defined",
+            "Cannot extend type Missing, which is not defined",
+            "Cannot extend type Role, Role is enum",
+            "Extension of enum Role adds value ADMIN, which is already defined",
+            "Cannot extend scalar Int, Int is builtin scalar",
+            "Extension of schema sets query, which is already set to Query",
+        ]
+    );
+}
+
+#[test]
+fn process_keyword_names_and_values() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Mode { ON_LINE OFF_LINE }
+        type Query { null: Int true(mode: Mode = OFF_LINE flag: Boolean = true): Boolean }",
+    )).unwrap();
+
+    let query = processed.query();
+    assert!(query.field("null").is_some());
+    let field = query.field("true").unwrap();
+    assert_eq!(
+        field.argument("mode").unwrap().default_value(),
+        Some(&Value::Enum(String::from("OFF_LINE")))
+    );
+    assert_eq!(
+        field.argument("flag").unwrap().default_value(),
+        Some(&Value::Boolean(true))
+    );
+}
+
+#[test]
+fn process_reserved_names_fail() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { __typename: String user(__id: ID): __User }
+        type __User { name: String }
+        enum Flag { true FALSE null __X }
+        directive @__internal on FIELD",
+    )).err()
+        .unwrap();
+
+    assert!(errors.iter().all(|e| e.code == Code::ReservedName));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type Query field __typename has reserved name, \
+             names starting with __ are used by introspection",
+            "Type Query field user argument __id has reserved name, \
+             names starting with __ are used by introspection",
+            "Type __User has reserved name, names starting with __ are used by introspection",
+            "Enum Flag value true is not allowed, enum values cannot be true, false or null",
+            "Enum Flag value null is not allowed, enum values cannot be true, false or null",
+            "Enum Flag value __X has reserved name, \
+             names starting with __ are used by introspection",
+            "Directive @__internal has reserved name, \
+             names starting with __ are used by introspection",
+        ]
+    );
+}
+
+#[test]
+fn process_enum_values() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Role {
+            \"can do anything\" ADMIN
+            USER @deprecated
+            GUEST @deprecated(reason: \"use USER\")
+            ROBOT @deprecated(reason: null)
+        }
+        type Query { role: Role }",
+    )).unwrap();
+
+    let role = processed.get_enum("Role").unwrap();
+    let values: Vec<_> = role.values().into_iter().collect();
+    assert_eq!(*values[0].name, "ADMIN");
+    assert_eq!(values[0].description().unwrap(), "can do anything");
+    assert!(!values[0].is_deprecated());
+    assert_eq!(values[0].deprecation_reason(), None);
+
+    assert!(values[1].is_deprecated());
+    assert_eq!(values[1].deprecation_reason(), Some("No longer supported"));
+    assert_eq!(values[2].deprecation_reason(), Some("use USER"));
+    assert!(values[3].is_deprecated());
+    assert_eq!(values[3].deprecation_reason(), None);
+
+    let guest = role.value("GUEST").unwrap();
+    assert!(guest.directive("deprecated").is_some());
+    assert_eq!(guest.directives().into_iter().count(), 1);
+}
+
+#[test]
+fn process_deprecations() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: User }
+        type User {
+            name: String @deprecated(reason: \"use fullName\")
+            fullName: String
+            posts(first: Int @deprecated, last: Int = 10 @deprecated(reason: null)): [String]
+        }
+        input Filter { at: String @deprecated id: ID }
+        enum Role { ADMIN @deprecated USER }
+        directive @cache(ttl: Int @deprecated) on FIELD_DEFINITION",
+    )).unwrap();
+
+    let user = processed.get_type("User").unwrap();
+    let name = user.field("name").unwrap();
+    assert!(name.is_deprecated());
+    assert_eq!(name.deprecation_reason(), Some("use fullName"));
+    assert!(!user.field("fullName").unwrap().is_deprecated());
+    let posts = user.field("posts").unwrap();
+    assert_eq!(
+        posts.argument("first").unwrap().deprecation_reason(),
+        Some("No longer supported")
+    );
+    assert!(posts.argument("last").unwrap().is_deprecated());
+    assert_eq!(posts.argument("last").unwrap().deprecation_reason(), None);
+    let filter = processed.get_input("Filter").unwrap();
+    assert!(filter.field("at").unwrap().is_deprecated());
+
+    let deprecations = processed.deprecations();
+    let coordinates: Vec<&str> = deprecations.iter().map(|d| d.coordinate.as_str()).collect();
+    assert_eq!(
+        coordinates,
+        vec![
+            "@cache(ttl:)",
+            "Filter.at",
+            "Role.ADMIN",
+            "User.name",
+            "User.posts(first:)",
+            "User.posts(last:)",
+        ]
+    );
+    assert_eq!(deprecations[3].reason, Some("use fullName"));
+    assert_eq!(deprecations[5].reason, None);
+    match deprecations[2].element {
+        SchemaElement::EnumValue(ref value) => assert_eq!(*value.name, "ADMIN"),
+        _ => panic!("Role.ADMIN is not enum value"),
+    }
+}
+
+#[test]
+fn process_deprecated_required_fails() {
+    let errors = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { user(id: ID! @deprecated, name: String! = \"\" @deprecated): String }
+        input Filter { at: String! @deprecated, limit: Int @deprecated }",
+    )).err()
+        .unwrap();
+
+    assert!(errors.iter().all(|e| e.code == Code::InvalidDirective));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type Query field user argument id is required and cannot be deprecated",
+            "Input Filter field at is required and cannot be deprecated",
+        ]
+    );
+}
+
+#[test]
+fn process_introspection_types_and_meta_fields() {
+    let processed = parse_gsdl(String::from(
+        "type Query { user: User }
+        type User { name: String }
+        union Result = User
+        enum Role { ADMIN }",
+    )).unwrap();
+
+    let types: Vec<&str> = processed.types().into_iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(types, vec!["Query", "User"]);
+    assert_eq!(processed.enums().into_iter().count(), 1);
+    let introspection: Vec<String> = processed
+        .introspection_types()
+        .into_iter()
+        .map(|t| String::from(t.name()))
+        .collect();
+    assert_eq!(
+        introspection,
+        vec![
+            "__Directive",
+            "__DirectiveLocation",
+            "__EnumValue",
+            "__Field",
+            "__InputValue",
+            "__Schema",
+            "__Type",
+            "__TypeKind",
+        ]
+    );
+    let schema = processed.get_type("__Schema").unwrap();
+    assert!(schema.field("queryType").is_some());
+    assert!(processed.get_enum("__TypeKind").unwrap().value("NON_NULL").is_some());
+
+    assert!(processed.meta_field("Query", "__schema").is_some());
+    let type_field = processed.meta_field("Query", "__type").unwrap();
+    assert!(type_field.argument("name").is_some());
+    assert!(processed.meta_field("User", "__schema").is_none());
+    assert!(processed.meta_field("User", "__typename").is_some());
+    assert!(processed.meta_field("Result", "__typename").is_some());
+    assert!(processed.meta_field("Role", "__typename").is_none());
+    assert!(processed.get_type("User").unwrap().field("__typename").is_none());
+    match processed.resolve("Query.__type(name:)") {
+        Some(SchemaElement::FieldArgument(argument)) => assert_eq!(*argument.name, "name"),
+        _ => panic!("Query.__type(name:) is not resolved to field argument"),
+    }
+    assert!(processed.resolve("__Type.ofType").is_some());
+
+    let errors = parse_gsdl(String::from("type Query { a: Int } type __Schema { a: Int }"))
+        .err()
+        .unwrap();
+    assert_eq!(
+        messages(errors),
+        vec!["Type __Schema has reserved name, names starting with __ are used by introspection"]
+    );
+}
+
+#[test]
+fn process_introspection_json() {
+    let processed = parse_gsdl(String::from(
+        "\"Entry point\"
+        type Query implements Node {
+            id: ID!
+            users(first: Int = 10, after: String @deprecated): [User!]!
+            old: String @deprecated(reason: \"use id\")
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! role: Role }
+        enum Role { ADMIN USER }
+        union Result = User | Query
+        input Filter { role: Role = ADMIN }",
+    )).unwrap();
+
+    let json = processed.to_introspection_json();
+    let schema = &json["__schema"];
+    assert_eq!(schema["queryType"], json!({"name": "Query"}));
+    assert!(schema["mutationType"].is_null());
+
+    let types = schema["types"].as_array().unwrap();
+    let names: Vec<&str> = types.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert!(names.contains(&"Int"));
+    assert!(names.contains(&"__Schema"));
+    let get = |name: &str| types.iter().find(|t| t["name"] == name).unwrap();
+
+    let query = get("Query");
+    assert_eq!(query["kind"], "OBJECT");
+    assert_eq!(query["description"], "Entry point");
+    assert_eq!(query["interfaces"], json!([{"kind": "INTERFACE", "name": "Node", "ofType": null}]));
+    assert!(query["enumValues"].is_null());
+    let users = &query["fields"][1];
+    assert_eq!(users["name"], "users");
+    assert_eq!(
+        users["args"],
+        json!([{
+            "name": "first",
+            "description": null,
+            "type": {"kind": "SCALAR", "name": "Int", "ofType": null},
+            "defaultValue": "10",
+        }])
+    );
+    assert_eq!(
+        users["type"],
+        json!({"kind": "NON_NULL", "name": null, "ofType":
+            {"kind": "LIST", "name": null, "ofType":
+                {"kind": "NON_NULL", "name": null, "ofType":
+                    {"kind": "OBJECT", "name": "User", "ofType": null}}}})
+    );
+    assert_eq!(query["fields"][2]["isDeprecated"], true);
+    assert_eq!(query["fields"][2]["deprecationReason"], "use id");
+
+    assert_eq!(
+        get("Node")["possibleTypes"],
+        json!([
+            {"kind": "OBJECT", "name": "Query", "ofType": null},
+            {"kind": "OBJECT", "name": "User", "ofType": null},
+        ])
+    );
+    assert_eq!(get("Result")["possibleTypes"][0]["name"], "User");
+    assert_eq!(get("Role")["enumValues"][1]["name"], "USER");
+    assert_eq!(get("Filter")["inputFields"][0]["defaultValue"], "ADMIN");
+
+    let directives = schema["directives"].as_array().unwrap();
+    let deprecated = directives.iter().find(|d| d["name"] == "deprecated").unwrap();
+    assert_eq!(deprecated["args"][0]["defaultValue"], "\"No longer supported\"");
+    assert_eq!(deprecated["locations"][0], "FIELD_DEFINITION");
+}
+
+#[test]
+fn process_introspection_json_round_trip() {
+    let processed = parse_gsdl(String::from(
+        "\"Entry point\"
+        type Query implements Node {
+            id: ID!
+            users(first: Int = 10, filter: Filter = {role: ADMIN, tags: [\"a\"]}): [User!]!
+            old: String @deprecated(reason: \"use id\")
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! role: Role \"when\" at: Date }
+        enum Role { ADMIN USER @deprecated }
+        union Result = User | Query
+        input Filter { role: Role = ADMIN, tags: [String!] }
+        scalar Date @specifiedBy(url: \"https://tools.ietf.org/html/rfc3339\")
+        directive @cache(ttl: Int! = 60) repeatable on FIELD_DEFINITION | OBJECT",
+    )).unwrap();
+    let json = processed.to_introspection_json();
+
+    let restored = parse_introspection_json(json.to_string()).unwrap();
+    assert_eq!(restored.to_introspection_json(), json);
+    let users = restored.query().field("users").unwrap();
+    assert_eq!(
+        users.argument("filter").unwrap().default_value(),
+        Some(&unprocessed::Value::Object(vec![
+            (String::from("role"), unprocessed::Value::Enum(String::from("ADMIN"))),
+            (
+                String::from("tags"),
+                unprocessed::Value::List(vec![unprocessed::Value::String(String::from("a"))]),
+            ),
+        ]))
+    );
+    assert_eq!(
+        restored.query().field("old").unwrap().deprecation_reason(),
+        Some("use id")
+    );
+    let user = restored.get_type("User").unwrap();
+    assert_eq!(user.field("at").unwrap().description().unwrap(), "when");
+    assert!(restored.get_enum("Role").unwrap().value("USER").unwrap().is_deprecated());
+    assert!(!restored.directive_definition("cache").unwrap().repeatable);
+
+    // wrapped response with fields of extended query
+    let restored = parse_introspection_json(format!(
+        "{{\"data\": {{\"__schema\": {{
+            \"queryType\": {{\"name\": \"Query\"}},
+            \"types\": [
+                {{\"kind\": \"OBJECT\", \"name\": \"Query\", \"fields\": [
+                    {{\"name\": \"at\", \"args\": [],
+                      \"type\": {{\"kind\": \"SCALAR\", \"name\": \"Date\"}}}}
+                ], \"interfaces\": []}},
+                {{\"kind\": \"SCALAR\", \"name\": \"Date\", \"specifiedByURL\": \"{}\"}}
+            ],
+            \"directives\": [
+                {{\"name\": \"cache\", \"locations\": [\"OBJECT\"], \"args\": [],
+                  \"isRepeatable\": true}}
+            ]
+        }}}}}}",
+        "https://tools.ietf.org/html/rfc3339"
+    )).unwrap();
+    let date = restored.get_scalar("Date").unwrap();
+    assert_eq!(date.specified_by_url.unwrap(), "https://tools.ietf.org/html/rfc3339");
+    assert!(restored.directive_definition("cache").unwrap().repeatable);
+}
+
+#[test]
+fn process_invalid_introspection_json_fails() {
+    assert_eq!(
+        messages(parse_introspection_json(String::from("{\"__schema\": ")).err().unwrap()),
+        vec!["Invalid JSON: EOF while parsing a value at line 1 column 13"]
+    );
+    assert_eq!(
+        messages(parse_introspection_json(String::from("{\"types\": []}")).err().unwrap()),
+        vec!["Introspection result has no __schema"]
+    );
+
+    let errors = parse_introspection_json(String::from(
+        "{\"__schema\": {
+            \"queryType\": {\"name\": \"Query\"},
+            \"types\": [
+                {\"kind\": \"OBJECT\", \"name\": \"Query\", \"fields\": [
+                    {\"name\": \"a\", \"args\": [], \"type\": {\"kind\": \"NON_NULL\"}}
+                ]},
+                {\"kind\": \"INPUT_OBJECT\", \"name\": \"Page\", \"inputFields\": [
+                    {\"name\": \"limit\", \"type\": {\"kind\": \"SCALAR\", \"name\": \"Int\"},
+                     \"defaultValue\": \"1.\"}
+                ]},
+                {\"kind\": \"TABLE\", \"name\": \"Row\"}
+            ],
+            \"directives\": [{\"name\": \"cache\", \"locations\": [], \"args\": []}]
+        }}",
+    )).err()
+        .unwrap();
+    assert!(errors.iter().all(|e| e.code == Code::Syntax));
+    assert_eq!(
+        messages(errors),
+        vec![
+            "Type Query field a has no type",
+            "Input Page field limit has invalid default value 1.: Invalid number 1.",
+            "Type Row has unknown kind TABLE",
+            "Directive @cache has no locations",
+        ]
+    );
+
+    let errors = parse_introspection_json(String::from(
+        "{\"__schema\": {\"queryType\": {\"name\": \"Query\"}, \"types\": [], \"directives\": []}}",
+    )).err()
+        .unwrap();
+    assert_eq!(messages(errors), vec!["Scheme query entry point Query type is not defined"]);
+
+    // there is no source to point to
+    let json = "{\"__schema\": {
+        \"queryType\": {\"name\": \"Query\"},
+        \"types\": [
+            {\"kind\": \"OBJECT\", \"name\": \"Query\", \"fields\": [
+                {\"name\": \"a\", \"args\": [], \"type\": {\"kind\": \"SCALAR\", \"name\": \"ID\"}},
+                {\"name\": \"a\", \"args\": [], \"type\": {\"kind\": \"SCALAR\", \"name\": \"ID\"}}
+            ]}
+        ],
+        \"directives\": []
+    }}";
+    let errors = parse_introspection_json(String::from(json)).err().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].span, errors[0].labels.len()), (None, 0));
+    assert_eq!(
+        errors[0].render(json),
+        "error[duplicate-definition]: Type Query has duplicate field named a\n"
+    );
+}
+
+#[test]
+fn process_lookup_by_name() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        interface Node { id: ID! }
+        type Query implements Node { id: ID! user(id: ID!): User }
+        type User { name: String posts(first: Int after: String): [Query] }
+        enum Role { ADMIN USER }",
+    )).unwrap();
+
+    assert_eq!(processed.get("Int").unwrap().name(), "Int");
+    assert_eq!(processed.get("Role").unwrap().name(), "Role");
+    assert!(processed.get("Missing").is_none());
+
+    assert_eq!(*processed.get_type("User").unwrap().name, "User");
+    assert_eq!(*processed.get_enum("Role").unwrap().name, "Role");
+    assert!(processed.get_type("Role").is_none());
+    assert!(processed.get_interface("Query").is_none());
+    assert!(processed.get_scalar("Int").is_none());
+
+    let user = processed.get_type("User").unwrap();
+    let posts = user.field("posts").unwrap();
+    assert_eq!(*posts.name, "posts");
+    assert_eq!(*posts.argument("first").unwrap().name, "first");
+    assert!(posts.argument("last").is_none());
+    assert!(user.field("missing").is_none());
+    let node = processed.get_interface("Node").unwrap();
+    assert_eq!(*node.field("id").unwrap().name, "id");
+}
+
+#[test]
+fn process_resolve_schema_coordinates() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        type Query { user(id: ID!): User }
+        type User { posts(first: Int): [Query] }
+        input Filter { at: String }
+        enum Role { ADMIN USER }",
+    )).unwrap();
+
+    match processed.resolve("User") {
+        Some(SchemaElement::Item(item)) => assert_eq!(item.name(), "User"),
+        _ => panic!("User is not resolved to item"),
+    }
+    match processed.resolve("User.posts") {
+        Some(SchemaElement::Field(field)) => assert_eq!(*field.name, "posts"),
+        _ => panic!("User.posts is not resolved to field"),
+    }
+    match processed.resolve("User.posts(first:)") {
+        Some(SchemaElement::FieldArgument(argument)) => assert_eq!(*argument.name, "first"),
+        _ => panic!("User.posts(first:) is not resolved to argument"),
+    }
+    match processed.resolve("Filter.at") {
+        Some(SchemaElement::InputField(field)) => assert_eq!(*field.name, "at"),
+        _ => panic!("Filter.at is not resolved to input field"),
+    }
+    match processed.resolve("Role.ADMIN") {
+        Some(SchemaElement::EnumValue(value)) => assert_eq!(*value.name, "ADMIN"),
+        _ => panic!("Role.ADMIN is not resolved to enum value"),
+    }
+    match processed.resolve("@deprecated") {
+        Some(SchemaElement::Directive(definition)) => assert_eq!(*definition.name, "deprecated"),
+        _ => panic!("@deprecated is not resolved to directive"),
+    }
+    match processed.resolve("@deprecated(reason:)") {
+        Some(SchemaElement::DirectiveArgument(argument)) => assert_eq!(*argument.name, "reason"),
+        _ => panic!("@deprecated(reason:) is not resolved to directive argument"),
+    }
+
+    for missing in &[
+        "Missing",
+        "User.missing",
+        "User.posts(last:)",
+        "Role.OWNER",
+        "Role.ADMIN(first:)",
+        "@missing",
+        "Int.value",
+    ] {
+        assert!(processed.resolve(missing).is_none(), "{} is resolved", missing);
+    }
+    for malformed in &["", "User.", ".posts", "User.posts(first)", "User(first:)", "@deprecated.reason", "User.posts.first"] {
+        assert!(processed.resolve(malformed).is_none(), "{} is resolved", malformed);
+    }
+}
+
+#[test]
+fn processed_lookup_errors() {
+    let mut data_map = GsdlDataMap::new();
+    data_map.insert(
+        String::from("Int"),
+        unprocessed::GsdlDataItem::Builtin(unprocessed::Scalar::Int),
+    );
+    data_map.insert(
+        String::from("Query"),
+        unprocessed::GsdlDataItem::Type(parse_Type("type Query { a: Missing b: Int }").unwrap()),
+    );
+
+    assert_eq!(
+        processed::Type::from("Missing", &data_map).err(),
+        Some(LookupError::NotFound(String::from("Missing")))
+    );
+    let error = processed::Enum::from("Int", &data_map).err().unwrap();
+    assert_eq!(error.to_string(), "Expected Int to be enum, but it is scalar");
+    assert!(processed::GsdlDataItem::from("Missing", &data_map).is_err());
+    assert_eq!(processed::GsdlDataItem::from("Int", &data_map).unwrap().name(), "Int");
+
+    // fields referencing unknown types fail only when their types are requested
+    let query = processed::Type::from("Query", &data_map).unwrap();
+    assert_eq!(
+        query.field("a").unwrap().field_type().err(),
+        Some(LookupError::NotFound(String::from("Missing")))
+    );
+    assert!(query.field("b").unwrap().field_type().is_ok());
+}
+
+#[test]
+fn process_keeps_source_order() {
+    let processed = parse_gsdl(String::from(
+        "scheme { query: Query }
+        enum Role { USER ADMIN GUEST }
+        type Query { user(name: String id: ID!): Role count: Int }",
+    )).unwrap();
+
+    let query = processed.query();
+    let names: Vec<&str> = query.fields().into_iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["user", "count"]);
+    let names: Vec<&str> = query
+        .sorted_fields()
+        .into_iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["count", "user"]);
+
+    let user = query.field("user").unwrap();
+    let names: Vec<&str> = user.arguments().into_iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "id"]);
+    let names: Vec<&str> = user
+        .sorted_arguments()
+        .into_iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["id", "name"]);
+
+    let role = processed.get_enum("Role").unwrap();
+    let names: Vec<&str> = role.values().into_iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["USER", "ADMIN", "GUEST"]);
+    let names: Vec<&str> = role
+        .sorted_values()
+        .into_iter()
+        .map(|v| v.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["ADMIN", "GUEST", "USER"]);
+    assert!(role.value("GUEST").is_some());
+
+    let owned = processed.owned();
+    let query = owned.query();
+    assert_eq!(query.fields[0].name, "user");
+    let names: Vec<&str> = query.sorted_fields().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["count", "user"]);
+    assert_eq!(query.field("count").unwrap().name, "count");
+}
+
+#[test]
+fn owned_scheme_outlives_processed() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<owned::Scheme>();
+
+    let scheme = parse_gsdl(String::from(
+        "\"Root\" schema { query: Query }
+        interface Node { id: ID! }
+        type Query implements Node { id: ID! user(id: ID! = 1): User }
+        type User { name: String roles: [Role!]! }
+        enum Role { ADMIN USER }
+        union Any = Query | User",
+    )).unwrap()
+        .owned();
+
+    let handle = thread::spawn(move || {
+        let query = scheme.query();
+        assert_eq!(query.name, "Query");
+        assert_eq!(scheme.description().unwrap(), "Root");
+        assert!(scheme.mutate().is_none());
+        assert_eq!(scheme.item(query.implements[0]).name(), "Node");
+
+        let user = query.field("user").unwrap();
+        assert_eq!(scheme.item(user.field_type.named_type()).name(), "User");
+        assert_eq!(
+            user.argument("id").unwrap().default,
+            Some(Value::Int(1))
+        );
+
+        let roles = match *scheme.get("User").unwrap() {
+            owned::Item::Type(ref user) => user.field("roles").unwrap().field_type.clone(),
+            ref other => panic!("unexpected item {:?}", other),
+        };
+        assert!(!roles.nullable);
+        match *scheme.item(roles.named_type()) {
+            owned::Item::Enum(ref role) => assert!(role.value("ADMIN").is_some()),
+            ref other => panic!("unexpected item {:?}", other),
+        }
+
+        match *scheme.get("Any").unwrap() {
+            owned::Item::Union(ref any) => assert_eq!(
+                any.members
+                    .iter()
+                    .map(|&id| scheme.item(id).name())
+                    .collect::<Vec<_>>(),
+                vec!["Query", "User"]
+            ),
+            ref other => panic!("unexpected item {:?}", other),
+        }
+        assert_eq!(scheme.get("Int").unwrap().name(), "Int");
+        assert!(scheme.get("Missing").is_none());
+        assert!(scheme.directive_definition("deprecated").is_some());
+    });
+    handle.join().unwrap();
+}
+
+#[test]
+fn owned_scheme_types_are_public() {
+    use std::sync::Arc;
+
+    let processed: ::Processed =
+        ::parse_gsdl(String::from("schema { query: Query } type Query { ids: [ID!] }")).unwrap();
+    let scheme: Arc<::Scheme> = processed.owned();
+    let query: &::Type = scheme.query();
+    let ids: &::Field = query.field("ids").unwrap();
+    let id: ::ItemId = ids.field_type.named_type();
+    match *scheme.item(id) {
+        ::Item::Builtin(scalar) => assert_eq!(scalar, ::Scalar::ID),
+        ref other => panic!("unexpected item {:?}", other),
+    }
+}
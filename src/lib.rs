@@ -1,3 +1,13 @@
-pub use graphql::parse_gsdl;
+extern crate lalrpop_util;
+#[macro_use]
+extern crate serde_json;
+
+pub use graphql::{parse_gsdl, parse_gsdl_lenient, parse_introspection_json, Processed};
+// owned scheme model, see Processed::owned()
+pub use graphql::{
+    Argument, CustomScalar, Directive, DirectiveDefinition, DirectiveLocation, Enum, EnumValue,
+    Field, InnerType, Input, Interface, Item, ItemId, OuterType, Scalar, Scheme, Sorted, Type,
+    Union, Value,
+};
 
 pub mod graphql;